@@ -0,0 +1,116 @@
+//! Timestamp sources for CUID generation.
+
+use core::fmt;
+
+// std::time::SystemTime panics on WASM, so use a different library there.
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+use std::time::{SystemTime, UNIX_EPOCH};
+#[cfg(all(feature = "std", target_family = "wasm"))]
+use web_time::{SystemTime, UNIX_EPOCH};
+
+/// A source of timestamps for CUID generation.
+///
+/// The default is the `SystemClock`, but any other source of time may be
+/// used, such as a mock clock in tests, a hybrid logical clock, or the clock
+/// of a simulation runtime.
+///
+/// `Clock` is implemented for any closure returning a `u128`:
+///
+/// ```
+/// use std::sync::atomic::{AtomicU64, Ordering};
+///
+/// use cuid_util::Clock;
+///
+/// // A clock which advances by one millisecond every time it is read
+/// let ticks = AtomicU64::new(0);
+/// let clock = move || u128::from(ticks.fetch_add(1, Ordering::Relaxed));
+///
+/// assert_eq!(0, clock.now_millis());
+/// assert_eq!(1, clock.now_millis());
+/// ```
+pub trait Clock: Send + Sync {
+    /// Returns the current time, in milliseconds since the Unix epoch.
+    fn now_millis(&self) -> u128;
+
+    /// Returns the current time, in milliseconds since the Unix epoch, or
+    /// an error if the time cannot be represented.
+    ///
+    /// The default implementation never fails. Clocks which might panic in
+    /// [`now_millis()`](Self::now_millis) should override this.
+    fn try_now_millis(&self) -> Result<u128, ClockError> {
+        Ok(self.now_millis())
+    }
+}
+impl<F> Clock for F
+where
+    F: Fn() -> u128 + Send + Sync,
+{
+    fn now_millis(&self) -> u128 {
+        self()
+    }
+}
+
+/// The system's wall clock.
+#[cfg(feature = "std")]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SystemClock;
+#[cfg(feature = "std")]
+impl Clock for SystemClock {
+    fn now_millis(&self) -> u128 {
+        self.try_now_millis()
+            // Panic safety: `.try_now_millis()` only fails if the system
+            // time is before 1970-01-01. It is impossible on Unix systems to set
+            // a time before then, since the entire system uses a 32 or 64 bit
+            // unsigned integer for time, where zero is midnight 1970-01-01.
+            //
+            // If you are on a system that for some reason both can be and needs to
+            // be set >50 years in the past AND this library not working is a
+            // problem for you, please feel free to reach out.
+            .expect(
+                "Failed to calculate system timestamp! Current system time may be \
+                 set to before the Unix epoch, or time may otherwise be broken. \
+                 Cannot continue",
+            )
+    }
+
+    fn try_now_millis(&self) -> Result<u128, ClockError> {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            // Use timestamp as milliseconds to match JS implementation
+            .map(|time| time.as_millis())
+            .map_err(|_| ClockError)
+    }
+}
+
+/// The error returned when a clock's time is before the Unix epoch.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ClockError;
+impl fmt::Display for ClockError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("system time is before the Unix epoch")
+    }
+}
+impl core::error::Error for ClockError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn system_clock() {
+        let expected = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_millis();
+        assert!(SystemClock.now_millis() - expected < 5);
+        assert!(SystemClock.try_now_millis().unwrap() - expected < 5);
+    }
+
+    #[test]
+    fn closure_clock() {
+        let clock = || 1234;
+        assert_eq!(1234, clock.now_millis());
+        assert_eq!(Ok(1234), clock.try_now_millis());
+    }
+}
@@ -0,0 +1,281 @@
+//! Fixed-capacity, stack-allocated storage for generated IDs.
+
+use alloc::{borrow::ToOwned, string::String};
+use core::{borrow::Borrow, cmp::Ordering, fmt, hash, ops::Deref};
+
+use crate::{MAX_BASE_36_DIGITS, encode_digits};
+
+/// The maximum number of characters that can be stored in an [`InlineId`].
+pub const INLINE_CAPACITY: usize = 32;
+
+/// A stack-allocated ID of up to [`INLINE_CAPACITY`] ASCII characters.
+///
+/// `InlineId` is `Copy` and takes up 33 bytes, making it a cheap alternative
+/// to a `String` when storing large numbers of IDs. It dereferences to `str`,
+/// so it can be used anywhere a string slice is expected.
+///
+/// ```
+/// use cuid_util::InlineId;
+///
+/// let mut id = InlineId::new();
+/// id.push_str("c");
+/// id.push_base_36_padded(35_u8, 4);
+///
+/// assert_eq!("c000z", &*id);
+/// assert_eq!(33, std::mem::size_of::<InlineId>());
+/// ```
+#[derive(Clone, Copy)]
+pub struct InlineId {
+    len: u8,
+    buf: [u8; INLINE_CAPACITY],
+}
+impl InlineId {
+    /// Creates an empty ID.
+    pub const fn new() -> Self {
+        Self {
+            len: 0,
+            buf: [0; INLINE_CAPACITY],
+        }
+    }
+
+    /// Returns the ID as a string slice.
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        // SAFETY: bytes are only ever added to the buffer from `&str`s or as
+        // ASCII digits, and `len` always falls on the boundary between them,
+        // so the buffer up to `len` is always valid UTF-8.
+        unsafe { core::str::from_utf8_unchecked(&self.buf[..self.len.into()]) }
+    }
+
+    /// The number of bytes remaining before the ID is full.
+    #[inline]
+    pub fn remaining_capacity(&self) -> usize {
+        INLINE_CAPACITY - usize::from(self.len)
+    }
+
+    /// Appends a character to the end of the ID.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the resulting ID would be longer than [`INLINE_CAPACITY`].
+    #[inline]
+    pub fn push(&mut self, ch: char) {
+        self.push_str(ch.encode_utf8(&mut [0; 4]));
+    }
+
+    /// Appends a string slice to the end of the ID.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the resulting ID would be longer than [`INLINE_CAPACITY`].
+    #[inline]
+    pub fn push_str(&mut self, s: &str) {
+        self.push_bytes(s.as_bytes());
+    }
+
+    /// Appends a number to the end of the ID as base 36.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the resulting ID would be longer than [`INLINE_CAPACITY`].
+    #[inline]
+    pub fn push_base_36<N: Into<u128>>(&mut self, number: N) {
+        let mut digits = [0; MAX_BASE_36_DIGITS];
+        let start = encode_digits(number.into(), &mut digits);
+        self.push_bytes(&digits[start..]);
+    }
+
+    /// Appends a number to the end of the ID as base 36, using exactly
+    /// `width` characters.
+    ///
+    /// Numbers which are too short are left-padded with zeros. Numbers which
+    /// are too long have their most significant digits removed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the resulting ID would be longer than [`INLINE_CAPACITY`].
+    #[inline]
+    pub fn push_base_36_padded<N: Into<u128>>(&mut self, number: N, width: usize) {
+        // Fill with zeros up front, so that any digits not written by the
+        // conversion act as padding.
+        let mut digits = [b'0'; MAX_BASE_36_DIGITS];
+        encode_digits(number.into(), &mut digits);
+        if width > MAX_BASE_36_DIGITS {
+            for _ in MAX_BASE_36_DIGITS..width {
+                self.push_bytes(b"0");
+            }
+            self.push_bytes(&digits);
+        } else {
+            self.push_bytes(&digits[MAX_BASE_36_DIGITS - width..]);
+        }
+    }
+
+    fn push_bytes(&mut self, bytes: &[u8]) {
+        let len = usize::from(self.len);
+        assert!(
+            bytes.len() <= self.remaining_capacity(),
+            "InlineId capacity of {INLINE_CAPACITY} exceeded"
+        );
+        self.buf[len..len + bytes.len()].copy_from_slice(bytes);
+        // Cast safety: we just checked that the new length does not exceed
+        // INLINE_CAPACITY, which fits in a u8.
+        self.len = (len + bytes.len()) as u8;
+    }
+}
+
+impl Default for InlineId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl Deref for InlineId {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+impl AsRef<str> for InlineId {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+impl Borrow<str> for InlineId {
+    fn borrow(&self) -> &str {
+        self.as_str()
+    }
+}
+impl fmt::Display for InlineId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+impl fmt::Debug for InlineId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+// Comparison and hashing are implemented by hand in terms of the string
+// slice, so that they agree with the `Borrow<str>` implementation.
+impl PartialEq for InlineId {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+impl Eq for InlineId {}
+impl PartialEq<str> for InlineId {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+impl PartialEq<&str> for InlineId {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+impl PartialOrd for InlineId {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl Ord for InlineId {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_str().cmp(other.as_str())
+    }
+}
+impl hash::Hash for InlineId {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state)
+    }
+}
+impl From<InlineId> for String {
+    fn from(id: InlineId) -> Self {
+        id.as_str().to_owned()
+    }
+}
+impl TryFrom<&str> for InlineId {
+    type Error = CapacityError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        if value.len() > INLINE_CAPACITY {
+            return Err(CapacityError);
+        }
+        let mut id = Self::new();
+        id.push_str(value);
+        Ok(id)
+    }
+}
+
+/// The error returned when a string is too long to fit in an [`InlineId`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CapacityError;
+impl fmt::Display for CapacityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "string is longer than {INLINE_CAPACITY} bytes")
+    }
+}
+impl core::error::Error for CapacityError {}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+    use crate::to_base_36;
+
+    use proptest::prelude::*;
+
+    #[test]
+    fn size() {
+        assert_eq!(33, std::mem::size_of::<InlineId>());
+    }
+
+    #[test]
+    fn push_and_compare() {
+        let mut id = InlineId::new();
+        id.push_str("abc");
+        id.push_base_36(35_u8);
+        assert_eq!("abcz", id.as_str());
+        assert_eq!(InlineId::try_from("abcz").unwrap(), id);
+        assert!(HashSet::from([id]).contains("abcz"));
+    }
+
+    #[test]
+    fn padding() {
+        let mut id = InlineId::new();
+        id.push_base_36_padded(0_u8, 4);
+        id.push_base_36_padded(u128::MAX, 28);
+        assert_eq!(format!("0000000{}", to_base_36(u128::MAX)), id.as_str());
+    }
+
+    #[test]
+    fn truncation() {
+        let mut id = InlineId::new();
+        id.push_base_36_padded(36_u32.pow(4) + 1, 4);
+        assert_eq!("0001", id.as_str());
+    }
+
+    #[test]
+    #[should_panic]
+    fn overflow() {
+        let mut id = InlineId::try_from("a".repeat(INLINE_CAPACITY).as_str()).unwrap();
+        id.push_str("a");
+    }
+
+    #[test]
+    fn too_long() {
+        assert_eq!(
+            Err(CapacityError),
+            InlineId::try_from("a".repeat(INLINE_CAPACITY + 1).as_str())
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn matches_to_base_36(n: u128) {
+            let mut id = InlineId::new();
+            id.push_base_36(n);
+            assert_eq!(to_base_36(n), id.as_str());
+        }
+    }
+}
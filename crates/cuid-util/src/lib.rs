@@ -1,9 +1,34 @@
 //! Common utility functions for CUID generation
+//!
+//! ## Features
+//!
+//! - `std` (enabled by default): provides the `SystemClock`. Without it,
+//!   this crate is `no_std`, but still requires `alloc`.
+
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+use alloc::string::{String, ToString};
+use core::fmt;
+
+mod clock;
+mod inline;
+mod wide;
+
+#[cfg(feature = "std")]
+pub use clock::SystemClock;
+pub use clock::{Clock, ClockError};
+pub use inline::{CapacityError, INLINE_CAPACITY, InlineId};
+pub use wide::{MAX_DIGITS_512, radix_digits_512};
 
 // =============================================================================
 // UTILITY FUNCTIONS
 // =============================================================================
 
+/// Number of digits required to represent `u128::MAX` in base 36.
+const MAX_BASE_36_DIGITS: usize = 25;
+
 // Construcing Base36 Values
 // =========================
 
@@ -64,6 +89,187 @@ pub fn to_base_36<N: Into<u128>>(number: N) -> String {
     buffer
 }
 
+/// Converts any number representable as a u128 into a base36 String of
+/// exactly `width` characters.
+///
+/// Numbers which are too short are left-padded with zeros. Numbers which are
+/// too long have their most significant digits removed.
+///
+/// ```
+/// use cuid_util::to_base_36_padded;
+///
+/// assert_eq!("000z", to_base_36_padded(35_u8, 4));
+/// assert_eq!("0001", to_base_36_padded(36_u32.pow(4) + 1, 4));
+/// ```
+pub fn to_base_36_padded<N: Into<u128>>(number: N, width: usize) -> String {
+    let mut buffer = String::with_capacity(width);
+    write_base_36_padded(number, width, &mut buffer)
+        // Panic safety: writing to a String never fails.
+        .expect("writing to a String is infallible");
+    buffer
+}
+
+/// Writes any number representable as a u128 as base36, without allocating.
+///
+/// ```
+/// use std::fmt::Write;
+///
+/// let mut out = String::from("id-");
+/// cuid_util::write_base_36(1295_u16, &mut out).unwrap();
+/// assert_eq!("id-zz", out);
+/// ```
+pub fn write_base_36<N: Into<u128>, W: fmt::Write + ?Sized>(number: N, out: &mut W) -> fmt::Result {
+    let mut digits = [0; MAX_BASE_36_DIGITS];
+    let start = encode_digits(number.into(), &mut digits);
+    out.write_str(digits_str(&digits[start..]))
+}
+
+/// Writes any number representable as a u128 as base36, using exactly
+/// `width` characters, without allocating.
+///
+/// See [`to_base_36_padded()`] for details of padding and truncation.
+pub fn write_base_36_padded<N: Into<u128>, W: fmt::Write + ?Sized>(
+    number: N,
+    width: usize,
+    out: &mut W,
+) -> fmt::Result {
+    // Fill with zeros up front, so that any digits not written by the
+    // conversion act as padding.
+    let mut digits = [b'0'; MAX_BASE_36_DIGITS];
+    encode_digits(number.into(), &mut digits);
+    if width > MAX_BASE_36_DIGITS {
+        for _ in MAX_BASE_36_DIGITS..width {
+            out.write_char('0')?;
+        }
+        out.write_str(digits_str(&digits))
+    } else {
+        out.write_str(digits_str(&digits[MAX_BASE_36_DIGITS - width..]))
+    }
+}
+
+/// Encodes any number representable as a u128 as base36 into the start of
+/// `buffer`, returning the encoded digits.
+///
+/// ```
+/// let mut buffer = [0; 8];
+/// assert_eq!(Ok("zz"), cuid_util::encode_base_36(1295_u16, &mut buffer));
+/// assert!(cuid_util::encode_base_36(u128::MAX, &mut buffer).is_err());
+/// ```
+pub fn encode_base_36<N: Into<u128>>(number: N, buffer: &mut [u8]) -> Result<&str, BufferTooSmall> {
+    let mut digits = [0; MAX_BASE_36_DIGITS];
+    let start = encode_digits(number.into(), &mut digits);
+    let digits = &digits[start..];
+    let target = buffer.get_mut(..digits.len()).ok_or(BufferTooSmall {
+        required: digits.len(),
+    })?;
+    target.copy_from_slice(digits);
+    Ok(digits_str(target))
+}
+
+/// Writes the base 36 representation of `number` to the end of `buf`,
+/// returning the index at which the digits begin.
+pub(crate) fn encode_digits(mut number: u128, buf: &mut [u8; MAX_BASE_36_DIGITS]) -> usize {
+    const RADIX: u128 = 36;
+    const DIGITS: &[u8; 36] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+
+    let mut idx = MAX_BASE_36_DIGITS;
+    loop {
+        idx -= 1;
+        // Index safety: the result of modulo RADIX is always less than 36.
+        buf[idx] = DIGITS[(number % RADIX) as usize];
+        number /= RADIX;
+        if number == 0 {
+            return idx;
+        }
+    }
+}
+
+/// Views digits written by [`encode_digits()`] as a string slice.
+fn digits_str(digits: &[u8]) -> &str {
+    // SAFETY: `encode_digits()` only writes ASCII digits and letters, and
+    // the padding is an ASCII zero, so the bytes are always valid UTF-8.
+    unsafe { core::str::from_utf8_unchecked(digits) }
+}
+
+/// The error returned when a buffer is too small to hold an encoded number.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BufferTooSmall {
+    /// The number of bytes required to hold the encoded number.
+    pub required: usize,
+}
+impl fmt::Display for BufferTooSmall {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "buffer too small, {} bytes required", self.required)
+    }
+}
+impl core::error::Error for BufferTooSmall {}
+
+// Parsing Base36 Values
+// =====================
+
+/// Parses a base36 string into a u128.
+///
+/// Digits may be upper- or lowercase. Unlike `u128::from_str_radix()`, no
+/// sign is accepted.
+///
+/// ```
+/// use cuid_util::{DecodeError, from_base_36};
+///
+/// assert_eq!(Ok(1295), from_base_36("zz"));
+/// assert_eq!(
+///     Err(DecodeError::InvalidDigit { position: 1, character: '!' }),
+///     from_base_36("z!")
+/// );
+/// assert_eq!(Err(DecodeError::Overflow), from_base_36(&"z".repeat(25)));
+/// ```
+pub fn from_base_36(digits: &str) -> Result<u128, DecodeError> {
+    if digits.is_empty() {
+        return Err(DecodeError::Empty);
+    }
+    digits
+        .chars()
+        .enumerate()
+        .try_fold(0_u128, |value, (position, character)| {
+            let digit = character.to_digit(36).ok_or(DecodeError::InvalidDigit {
+                position,
+                character,
+            })?;
+            value
+                .checked_mul(36)
+                .and_then(|value| value.checked_add(digit.into()))
+                .ok_or(DecodeError::Overflow)
+        })
+}
+
+/// The error returned when a string cannot be parsed as base 36.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DecodeError {
+    /// The string is empty.
+    Empty,
+    /// A character is not a base 36 digit. Positions are in characters,
+    /// starting from zero.
+    InvalidDigit { position: usize, character: char },
+    /// The number is too large to fit in a u128.
+    Overflow,
+}
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => f.write_str("cannot parse base 36 from an empty string"),
+            Self::InvalidDigit {
+                position,
+                character,
+            } => write!(
+                f,
+                "invalid base 36 digit {character:?} at position {position}"
+            ),
+            Self::Overflow => f.write_str("base 36 number is too large for a u128"),
+        }
+    }
+}
+impl core::error::Error for DecodeError {}
+
 /// Trait for types that can be converted to base 36.
 pub trait ToBase36 {
     fn to_base_36(self) -> String;
@@ -103,5 +309,92 @@ mod tests {
                 &val
             )
         }
+
+        #[test]
+        fn round_trip(n: u128) {
+            assert_eq!(Ok(n), from_base_36(&to_base_36(n)));
+            assert_eq!(Ok(n), from_base_36(&to_base_36(n).to_uppercase()));
+        }
+
+        #[test]
+        fn matches_from_str_radix(s in "[0-9a-z]{1,30}") {
+            assert_eq!(u128::from_str_radix(&s, 36).ok(), from_base_36(&s).ok());
+        }
+
+        #[test]
+        fn padded_output(n: u128, width in 0_usize..40) {
+            let val = to_base_36(n);
+            let expected = if val.len() > width {
+                val[val.len() - width..].to_string()
+            } else {
+                format!("{val:0>width$}")
+            };
+            assert_eq!(expected, to_base_36_padded(n, width));
+        }
+
+        #[test]
+        fn non_allocating_output(n: u128) {
+            let mut written = String::new();
+            write_base_36(n, &mut written).unwrap();
+            assert_eq!(to_base_36(n), written);
+
+            let mut buffer = [0; MAX_BASE_36_DIGITS];
+            assert_eq!(Ok(to_base_36(n).as_str()), encode_base_36(n, &mut buffer));
+        }
+    }
+
+    #[test]
+    fn decode_errors() {
+        assert_eq!(Err(DecodeError::Empty), from_base_36(""));
+        assert_eq!(
+            Err(DecodeError::InvalidDigit {
+                position: 0,
+                character: '+'
+            }),
+            from_base_36("+1")
+        );
+        assert_eq!(
+            Err(DecodeError::Overflow),
+            from_base_36(&format!("1{}", "0".repeat(MAX_BASE_36_DIGITS)))
+        );
+        assert_eq!(Ok(u128::MAX), from_base_36(&to_base_36(u128::MAX)));
+    }
+
+    #[test]
+    fn padding() {
+        assert_eq!("00", to_base_36_padded(0_u8, 2));
+        assert_eq!("", to_base_36_padded(35_u8, 0));
+        assert_eq!("00foo", to_base_36_padded(from_base_36("foo").unwrap(), 5));
+        assert_eq!("o", to_base_36_padded(from_base_36("foo").unwrap(), 1));
+    }
+
+    #[test]
+    fn does_not_pad_number_of_width() {
+        assert_eq!("foo", to_base_36_padded(from_base_36("foo").unwrap(), 3));
+    }
+
+    #[test]
+    fn single_char_pad() {
+        assert_eq!("0foo", to_base_36_padded(from_base_36("foo").unwrap(), 4));
+    }
+
+    #[test]
+    fn multichar_pad() {
+        assert_eq!("000foo", to_base_36_padded(from_base_36("foo").unwrap(), 6));
+        assert_eq!(format!("{}1", "0".repeat(29)), to_base_36_padded(1_u8, 30));
+    }
+
+    #[test]
+    fn smaller_pad() {
+        assert_eq!("c", to_base_36_padded(from_base_36("abc").unwrap(), 1));
+        assert_eq!("bc", to_base_36_padded(from_base_36("abc").unwrap(), 2));
+    }
+
+    #[test]
+    fn buffer_too_small() {
+        assert_eq!(
+            Err(BufferTooSmall { required: 3 }),
+            encode_base_36(46_655_u16, &mut [0; 2])
+        );
     }
 }
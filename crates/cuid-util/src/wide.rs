@@ -0,0 +1,254 @@
+//! Radix conversion of 512-bit numbers, such as SHA3-512 digests.
+
+/// Number of digits required to represent a 512-bit number in the smallest
+/// radix, 2.
+pub const MAX_DIGITS_512: usize = 512;
+
+/// Number of 64-bit limbs in a 512-bit number.
+const LIMBS: usize = 8;
+
+/// Converts a 512-bit big-endian number, such as a SHA3-512 digest, into
+/// its lowest `count` digits of the given radix, most significant first.
+///
+/// The digits are written to the end of `buffer`, and the returned slice
+/// contains only the digits themselves. If the number has no more than
+/// `count` digits, they are all returned, without leading zeros (zero itself
+/// is a single `0` digit). Digits are returned as values from `0` to
+/// `radix - 1`, to be mapped to characters by the caller.
+///
+/// This produces the same digits as the end of `num::BigUint::to_radix_be()`,
+/// but without allocating. The number is divided by the largest power of the
+/// radix which fits in a `u64`, yielding several digits per division, and
+/// division stops as soon as `count` digits have been produced. Pass
+/// [`MAX_DIGITS_512`] to convert the whole number.
+///
+/// ```
+/// use cuid_util::{MAX_DIGITS_512, radix_digits_512};
+///
+/// let mut number = [0; 64];
+/// number[62] = 0x1c;
+/// number[63] = 0x1f;
+/// let mut buffer = [0; MAX_DIGITS_512];
+/// assert_eq!(&[5, 19, 35], radix_digits_512(&number, 36, MAX_DIGITS_512, &mut buffer));
+/// assert_eq!(&[19, 35], radix_digits_512(&number, 36, 2, &mut buffer));
+/// ```
+///
+/// # Panics
+///
+/// Panics if `radix` is less than 2.
+pub fn radix_digits_512<'a>(
+    number: &[u8; 64],
+    radix: u8,
+    count: usize,
+    buffer: &'a mut [u8; MAX_DIGITS_512],
+) -> &'a [u8] {
+    assert!(radix >= 2, "radix must be at least 2");
+    if count == 0 {
+        return &[];
+    }
+    // Base 36 is by far the most common radix, so let the compiler specialize
+    // it, replacing division by the radix with multiplication
+    match radix {
+        36 => convert(number, 36, count, buffer),
+        _ => convert(number, radix, count, buffer),
+    }
+}
+
+#[inline(always)]
+fn convert<'a>(
+    number: &[u8; 64],
+    radix: u8,
+    count: usize,
+    buffer: &'a mut [u8; MAX_DIGITS_512],
+) -> &'a [u8] {
+    let radix = u64::from(radix);
+    // No number has more digits than the buffer holds
+    let stop = MAX_DIGITS_512.saturating_sub(count);
+
+    // The largest power of the radix which fits in a u64, and its exponent
+    let (mut chunk_divisor, mut chunk_digits) = (radix, 1);
+    while let Some(next) = chunk_divisor.checked_mul(radix) {
+        chunk_divisor = next;
+        chunk_digits += 1;
+    }
+    let chunk_divisor = Divisor::new(chunk_divisor);
+
+    let mut limbs = [0_u64; LIMBS];
+    for (limb, bytes) in limbs.iter_mut().zip(number.as_chunks::<8>().0) {
+        *limb = u64::from_be_bytes(*bytes);
+    }
+    // Skip leading zero limbs, which are left behind as the number shrinks
+    let mut first = limbs.iter().position(|&limb| limb != 0).unwrap_or(LIMBS);
+
+    let mut end = MAX_DIGITS_512;
+    while first < LIMBS && end > stop {
+        // Divide the number by the chunk divisor in place, keeping the
+        // remainder, which holds the next `chunk_digits` digits
+        let mut remainder = 0;
+        for limb in &mut limbs[first..] {
+            (*limb, remainder) = chunk_divisor.div_rem(remainder, *limb);
+        }
+        while first < LIMBS && limbs[first] == 0 {
+            first += 1;
+        }
+
+        let mut chunk = remainder;
+        for _ in 0..chunk_digits {
+            end -= 1;
+            // Cast safety: the digit is less than the radix, a u8
+            buffer[end] = (chunk % radix) as u8;
+            chunk /= radix;
+            // The most significant chunk is not padded with zeros
+            if end == stop || (first == LIMBS && chunk == 0) {
+                break;
+            }
+        }
+    }
+
+    if end == MAX_DIGITS_512 {
+        // The number is zero
+        end -= 1;
+        buffer[end] = 0;
+    }
+    &buffer[end..]
+}
+
+/// A divisor with a precomputed reciprocal.
+///
+/// Hardware division is slow, and since our divisors are not constants, the
+/// compiler cannot replace division with multiplication for us. This uses
+/// the algorithm from Möller and Granlund, "Improved division by invariant
+/// integers" (2011), to divide with two multiplications instead.
+struct Divisor {
+    /// The divisor, shifted left so that its highest bit is set.
+    normalized: u64,
+    shift: u32,
+    /// `floor((2^128 - 1) / normalized) - 2^64`
+    reciprocal: u64,
+}
+impl Divisor {
+    fn new(divisor: u64) -> Self {
+        let shift = divisor.leading_zeros();
+        let normalized = divisor << shift;
+        Self {
+            normalized,
+            shift,
+            // Cast safety: since the highest bit of `normalized` is set, the
+            // quotient is at least 2^64 and less than 2^65
+            reciprocal: (u128::MAX / u128::from(normalized) - (1 << 64)) as u64,
+        }
+    }
+
+    /// Divides `high * 2^64 + low` by the divisor, returning the quotient and
+    /// the remainder. `high` must be less than the divisor, so that the
+    /// quotient fits in a `u64`.
+    #[inline]
+    fn div_rem(&self, high: u64, low: u64) -> (u64, u64) {
+        // Shift the dividend along with the divisor, which leaves the
+        // quotient unchanged and shifts the remainder
+        let (high, low) = match self.shift {
+            0 => (high, low),
+            shift => ((high << shift) | (low >> (64 - shift)), low << shift),
+        };
+
+        let estimate = u128::from(self.reciprocal) * u128::from(high)
+            + ((u128::from(high) << 64) | u128::from(low));
+        // Cast safety: truncation is intended, to split the estimate into
+        // its high and low words
+        let mut quotient = ((estimate >> 64) as u64).wrapping_add(1);
+        let estimate_low = estimate as u64;
+
+        let mut remainder = low.wrapping_sub(quotient.wrapping_mul(self.normalized));
+        if remainder > estimate_low {
+            quotient = quotient.wrapping_sub(1);
+            remainder = remainder.wrapping_add(self.normalized);
+        }
+        if remainder >= self.normalized {
+            quotient += 1;
+            remainder -= self.normalized;
+        }
+        (quotient, remainder >> self.shift)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use num::BigUint;
+    use proptest::prelude::*;
+
+    #[test]
+    fn extremes() {
+        let mut buffer = [0; MAX_DIGITS_512];
+        assert_eq!(
+            &[0],
+            radix_digits_512(&[0; 64], 36, MAX_DIGITS_512, &mut buffer)
+        );
+        assert_eq!(
+            &[1; 512],
+            radix_digits_512(&[255; 64], 2, MAX_DIGITS_512, &mut buffer)
+        );
+        assert_eq!(
+            BigUint::from_bytes_be(&[255; 64]).to_radix_be(36),
+            radix_digits_512(&[255; 64], 36, MAX_DIGITS_512, &mut buffer)
+        );
+
+        let mut one = [0; 64];
+        one[63] = 1;
+        assert_eq!(
+            &[1],
+            radix_digits_512(&one, 255, MAX_DIGITS_512, &mut buffer)
+        );
+
+        assert!(radix_digits_512(&[255; 64], 36, 0, &mut buffer).is_empty());
+        assert_eq!(&[0], radix_digits_512(&[0; 64], 36, 1, &mut buffer));
+        assert_eq!(&[1; 3], radix_digits_512(&[255; 64], 2, 3, &mut buffer));
+    }
+
+    #[test]
+    #[should_panic = "radix must be at least 2"]
+    fn radix_one() {
+        radix_digits_512(&[0; 64], 1, MAX_DIGITS_512, &mut [0; MAX_DIGITS_512]);
+    }
+
+    proptest! {
+        #[test]
+        fn matches_biguint(number in prop::array::uniform32(any::<u8>()), low in prop::array::uniform32(any::<u8>()), radix in 2_u8..=255) {
+            let mut bytes = [0; 64];
+            bytes[..32].copy_from_slice(&number);
+            bytes[32..].copy_from_slice(&low);
+
+            let mut buffer = [0; MAX_DIGITS_512];
+            assert_eq!(
+                BigUint::from_bytes_be(&bytes).to_radix_be(u32::from(radix)),
+                radix_digits_512(&bytes, radix, MAX_DIGITS_512, &mut buffer)
+            );
+        }
+
+        #[test]
+        fn truncated_matches_suffix(bytes in prop::collection::vec(any::<u8>(), 64), leading_zeros in 0_usize..64, radix in 2_u8..=255, count in 0_usize..=MAX_DIGITS_512) {
+            let mut number = [0; 64];
+            number[leading_zeros..].copy_from_slice(&bytes[leading_zeros..]);
+
+            let full = BigUint::from_bytes_be(&number).to_radix_be(u32::from(radix));
+            let suffix = &full[full.len().saturating_sub(count)..];
+            let mut buffer = [0; MAX_DIGITS_512];
+            assert_eq!(suffix, radix_digits_512(&number, radix, count, &mut buffer));
+        }
+
+        #[test]
+        fn matches_biguint_base_36(bytes in prop::collection::vec(any::<u8>(), 64), leading_zeros in 0_usize..64) {
+            // Exercise numbers of every size, not just those close to 2^512
+            let mut number = [0; 64];
+            number[leading_zeros..].copy_from_slice(&bytes[leading_zeros..]);
+
+            let mut buffer = [0; MAX_DIGITS_512];
+            let digits = radix_digits_512(&number, 36, MAX_DIGITS_512, &mut buffer)
+                .iter()
+                .map(|&digit| char::from_digit(digit.into(), 36).unwrap())
+                .collect::<String>();
+            assert_eq!(BigUint::from_bytes_be(&number).to_str_radix(36), digits);
+        }
+    }
+}
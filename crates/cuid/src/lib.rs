@@ -85,11 +85,26 @@
 //! ## Features
 //! - `v1` (enabled by default): provides access to v1 CUIDs
 //! - `v2` (enabled by default): provides access to v2 CUIDs
+//! - `serde`: serialization and validating deserialization for typed CUIDs,
+//!   via the `serde` features of the enabled sub-crates
+//! - `futures`: an endless `Stream` of v2 CUIDs, via
+//!   `Cuid2Constructor::stream()`
+//! - `sqlx`: sqlx `Type`, `Encode`, and `Decode` implementations for typed
+//!   CUIDs, which are stored as text and validated when decoded, via the
+//!   `sqlx` features of the enabled sub-crates
+//! - `diesel`: Diesel `Text` column support for typed CUIDs, which are
+//!   validated when loaded, via the `diesel` features of the enabled
+//!   sub-crates
+//! - `rusqlite`: rusqlite `ToSql` and `FromSql` implementations for typed
+//!   CUIDs, which are validated when read, and `cuid()` and `cuid2()` SQLite
+//!   functions (see `cuid1::rusqlite` and `cuid2::rusqlite`), via the
+//!   `rusqlite` features of the enabled sub-crates
 //!
 
 #[cfg(feature = "v1")]
 pub use cuid1::{
-    self as v1, cuid as cuid1, is_cuid as is_cuid1, is_slug as is_cuid1_slug, slug as cuid1_slug,
+    self as v1, Cuid1, cuid as cuid1, is_cuid as is_cuid1, is_cuid_strict as is_cuid1_strict,
+    is_slug as is_cuid1_slug, slug as cuid1_slug, validate_cuid as validate_cuid1,
 };
 #[cfg(feature = "v1")]
 #[doc(hidden)]
@@ -97,10 +112,23 @@ pub use cuid1::{one_off_cuid1, one_off_cuid1_slug};
 
 #[cfg(feature = "v2")]
 pub use cuid2::{
-    self as v2, CuidConstructor as Cuid2Constructor, cuid as cuid2, is_cuid2,
-    is_slug as is_cuid2_slug, slug as cuid2_slug,
+    self as v2, Cuid2, CuidConstructor as Cuid2Constructor, cuid as cuid2, is_cuid2,
+    is_slug as is_cuid2_slug, slug as cuid2_slug, validate_cuid2,
 };
 
+/// Reinitialize the counters, fingerprints, and random number generators of
+/// all enabled CUID versions.
+///
+/// Call this in a child process immediately after `fork()`, so that it does
+/// not generate the same IDs as its parent or siblings. See
+/// [`cuid1::reseed()`] and [`cuid2::reseed()`] for details.
+pub fn reseed() {
+    #[cfg(feature = "v1")]
+    cuid1::reseed();
+    #[cfg(feature = "v2")]
+    cuid2::reseed();
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
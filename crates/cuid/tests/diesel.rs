@@ -0,0 +1,123 @@
+//! Diesel tests shared by every CUID type.
+//!
+//! The same round trip and validation checks run against [`Cuid1`],
+//! [`Cuid2`], and a type from [`define_cuid!`](cuid::v2::define_cuid).
+
+#![cfg(all(
+    feature = "diesel",
+    feature = "v1",
+    feature = "v2",
+    not(target_family = "wasm")
+))]
+
+use cuid::{Cuid1, Cuid2};
+use diesel::{dsl::select, prelude::*, sql_types::Text};
+
+cuid::v2::define_cuid!(UserId, "usr");
+cuid::v2::define_cuid!(OrderId, "ord");
+
+fn connect() -> SqliteConnection {
+    let mut conn = SqliteConnection::establish(":memory:").unwrap();
+    diesel::sql_query("CREATE TABLE users (id TEXT PRIMARY KEY, parent TEXT)")
+        .execute(&mut conn)
+        .unwrap();
+    conn
+}
+
+macro_rules! diesel_tests {
+    ($($module:ident: $ty:ty;)+) => {$(
+        mod $module {
+            use std::str::FromStr;
+
+            use diesel::{prelude::*, result::Error as DieselError};
+
+            use super::*;
+
+            diesel::table! {
+                users (id) {
+                    id -> Text,
+                    parent -> Nullable<Text>,
+                }
+            }
+
+            #[derive(Debug, PartialEq, Queryable, Insertable)]
+            struct User {
+                id: $ty,
+                parent: Option<$ty>,
+            }
+
+            #[test]
+            fn round_trip() {
+                let mut conn = connect();
+                let parent = User {
+                    id: <$ty>::new(),
+                    parent: None,
+                };
+                let child = User {
+                    id: <$ty>::new(),
+                    parent: Some(parent.id.clone()),
+                };
+                diesel::insert_into(users::table)
+                    .values([&parent, &child])
+                    .execute(&mut conn)
+                    .unwrap();
+
+                let found: User = users::table
+                    .filter(users::parent.eq(&parent.id))
+                    .first(&mut conn)
+                    .unwrap();
+                assert_eq!(child, found);
+                let found: User = users::table.find(&parent.id).first(&mut conn).unwrap();
+                assert_eq!(parent, found);
+            }
+
+            #[test]
+            fn invalid() {
+                let mut conn = connect();
+                diesel::insert_into(users::table)
+                    .values(users::id.eq("Not-A-Cuid"))
+                    .execute(&mut conn)
+                    .unwrap();
+
+                let err = users::table
+                    .select(users::id)
+                    .first::<$ty>(&mut conn)
+                    .unwrap_err();
+                let DieselError::DeserializationError(source) = err else {
+                    panic!("unexpected error: {err}");
+                };
+                // Diesel wraps the error with the name of the field
+                let source = source.source().expect("field error");
+                assert_eq!(
+                    Some(&"Not-A-Cuid".parse::<$ty>().unwrap_err()),
+                    source.downcast_ref::<<$ty as FromStr>::Err>()
+                );
+            }
+        }
+    )+};
+}
+
+diesel_tests! {
+    cuid1: Cuid1;
+    cuid2: Cuid2;
+    prefixed: UserId;
+}
+
+#[test]
+fn prefixed_constructor() {
+    let mut conn = connect();
+    let fetched: UserId = select(UserId::constructor().into_sql::<Text>())
+        .get_result(&mut conn)
+        .unwrap();
+    assert!(fetched.as_str().starts_with("usr_"));
+}
+
+#[test]
+fn wrong_prefix() {
+    let mut conn = connect();
+    assert!(
+        select(OrderId::new().into_sql::<Text>())
+            .get_result::<UserId>(&mut conn)
+            .is_err()
+    );
+}
@@ -0,0 +1,112 @@
+//! rusqlite tests shared by every CUID type.
+//!
+//! The same round trip and validation checks run against [`Cuid1`],
+//! [`Cuid2`], and a type from [`define_cuid!`](cuid::v2::define_cuid).
+
+#![cfg(all(
+    feature = "rusqlite",
+    feature = "v1",
+    feature = "v2",
+    not(target_family = "wasm")
+))]
+
+use std::{fmt::Debug, str::FromStr};
+
+use cuid::{Cuid1, Cuid2};
+use rusqlite::{
+    Connection, Error,
+    types::{FromSql, ToSql},
+};
+
+cuid::v2::define_cuid!(UserId, "usr");
+cuid::v2::define_cuid!(OrderId, "ord");
+
+fn round_trip<T: FromSql + ToSql + Debug + PartialEq>(id: T) {
+    let conn = Connection::open_in_memory().unwrap();
+    conn.execute("CREATE TABLE users (id TEXT PRIMARY KEY, parent TEXT)", [])
+        .unwrap();
+
+    conn.execute(
+        "INSERT INTO users (id, parent) VALUES (?1, ?2)",
+        (&id, None::<T>),
+    )
+    .unwrap();
+
+    let (fetched, parent): (T, Option<T>) = conn
+        .query_row("SELECT id, parent FROM users", [], |row| {
+            Ok((row.get(0)?, row.get(1)?))
+        })
+        .unwrap();
+    assert_eq!(id, fetched);
+    assert_eq!(None, parent);
+}
+
+fn invalid<T>()
+where
+    T: FromSql + FromStr + Debug,
+    T::Err: std::error::Error + PartialEq + 'static,
+{
+    let conn = Connection::open_in_memory().unwrap();
+    let err = conn
+        .query_row("SELECT 'Not-A-Cuid'", [], |row| row.get::<_, T>(0))
+        .unwrap_err();
+    let Error::FromSqlConversionFailure(_, _, source) = err else {
+        panic!("unexpected error: {err}");
+    };
+    assert_eq!(
+        Some(&"Not-A-Cuid".parse::<T>().unwrap_err()),
+        source.downcast_ref::<T::Err>()
+    );
+
+    let err = conn
+        .query_row("SELECT 12", [], |row| row.get::<_, T>(0))
+        .unwrap_err();
+    assert!(
+        matches!(err, Error::InvalidColumnType(..)),
+        "unexpected error: {err}"
+    );
+}
+
+macro_rules! rusqlite_tests {
+    ($($module:ident: $ty:ty;)+) => {$(
+        mod $module {
+            use super::*;
+
+            #[test]
+            fn round_trip() {
+                super::round_trip(<$ty>::new());
+            }
+
+            #[test]
+            fn invalid() {
+                super::invalid::<$ty>();
+            }
+        }
+    )+};
+}
+
+rusqlite_tests! {
+    cuid1: Cuid1;
+    cuid2: Cuid2;
+    prefixed: UserId;
+}
+
+#[test]
+fn prefixed_constructor() {
+    let conn = Connection::open_in_memory().unwrap();
+    cuid::v2::rusqlite::register_constructor(&conn, "user_id", UserId::constructor()).unwrap();
+    let id: UserId = conn
+        .query_row("SELECT user_id()", [], |row| row.get(0))
+        .unwrap();
+    assert!(id.as_str().starts_with("usr_"));
+}
+
+#[test]
+fn wrong_prefix() {
+    let conn = Connection::open_in_memory().unwrap();
+    assert!(
+        conn.query_row("SELECT ?1", [&OrderId::new()], |row| row
+            .get::<_, UserId>(0))
+            .is_err()
+    );
+}
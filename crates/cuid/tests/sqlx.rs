@@ -0,0 +1,112 @@
+//! sqlx tests shared by every CUID type.
+//!
+//! The same round trip and validation checks run against [`Cuid1`],
+//! [`Cuid2`], and a type from [`define_cuid!`](cuid::v2::define_cuid).
+
+#![cfg(all(
+    feature = "sqlx",
+    feature = "v1",
+    feature = "v2",
+    not(target_family = "wasm")
+))]
+
+use std::{error::Error, fmt::Debug, str::FromStr};
+
+use cuid::{Cuid1, Cuid2};
+use sqlx::{Connection, Decode, Encode, Sqlite, SqliteConnection, Type, query, query_scalar};
+
+cuid::v2::define_cuid!(UserId, "usr");
+cuid::v2::define_cuid!(OrderId, "ord");
+
+async fn connect() -> SqliteConnection {
+    SqliteConnection::connect("sqlite::memory:").await.unwrap()
+}
+
+async fn round_trip<T>(id: T)
+where
+    T: for<'r> Decode<'r, Sqlite> + for<'q> Encode<'q, Sqlite> + Type<Sqlite>,
+    T: Debug + PartialEq + Send + Unpin,
+{
+    let mut conn = connect().await;
+    query("CREATE TABLE users (id TEXT PRIMARY KEY, parent TEXT)")
+        .execute(&mut conn)
+        .await
+        .unwrap();
+
+    query("INSERT INTO users (id, parent) VALUES (?, ?)")
+        .bind(&id)
+        .bind(None::<T>)
+        .execute(&mut conn)
+        .await
+        .unwrap();
+
+    let (fetched, parent): (T, Option<T>) = sqlx::query_as("SELECT id, parent FROM users")
+        .fetch_one(&mut conn)
+        .await
+        .unwrap();
+    assert_eq!(id, fetched);
+    assert_eq!(None, parent);
+}
+
+async fn invalid<T>()
+where
+    T: for<'r> Decode<'r, Sqlite> + Type<Sqlite> + FromStr + Debug + Send + Unpin,
+    T::Err: Error + PartialEq + 'static,
+{
+    let mut conn = connect().await;
+    let err = query_scalar::<_, T>("SELECT 'Not-A-Cuid'")
+        .fetch_one(&mut conn)
+        .await
+        .unwrap_err();
+    let sqlx::Error::ColumnDecode { source, .. } = err else {
+        panic!("unexpected error: {err}");
+    };
+    assert_eq!(
+        Some(&"Not-A-Cuid".parse::<T>().unwrap_err()),
+        source.downcast_ref::<T::Err>()
+    );
+
+    // Values of other types are rejected before validation
+    assert!(
+        query_scalar::<_, T>("SELECT 12")
+            .fetch_one(&mut conn)
+            .await
+            .is_err()
+    );
+}
+
+macro_rules! sqlx_tests {
+    ($($module:ident: $ty:ty;)+) => {$(
+        mod $module {
+            use super::*;
+
+            #[tokio::test]
+            async fn round_trip() {
+                super::round_trip(<$ty>::new()).await;
+            }
+
+            #[tokio::test]
+            async fn invalid() {
+                super::invalid::<$ty>().await;
+            }
+        }
+    )+};
+}
+
+sqlx_tests! {
+    cuid1: Cuid1;
+    cuid2: Cuid2;
+    prefixed: UserId;
+}
+
+#[tokio::test]
+async fn wrong_prefix() {
+    let mut conn = connect().await;
+    assert!(
+        query_scalar::<_, UserId>("SELECT ?")
+            .bind(OrderId::new())
+            .fetch_one(&mut conn)
+            .await
+            .is_err()
+    );
+}
@@ -6,6 +6,10 @@ fn bench_cuid(c: &mut Criterion) {
     c.bench_function("generate cuid", |b| b.iter(cuid1::cuid));
 }
 
+fn bench_cuid_inline(c: &mut Criterion) {
+    c.bench_function("generate inline cuid", |b| b.iter(cuid1::cuid_inline));
+}
+
 fn bench_many_cuids(c: &mut Criterion) {
     c.bench_function("generate many cuids", |b| {
         b.iter(|| {
@@ -77,6 +81,7 @@ fn bench_multithread_perf_lots_of_threads(c: &mut Criterion) {
 criterion_group!(
     benches,
     bench_cuid,
+    bench_cuid_inline,
     bench_slug,
     bench_many_cuids,
     bench_many_slugs,
@@ -1,12 +1,15 @@
 use std::sync::atomic::Ordering;
 
-use crate::text::{pad, to_base_string};
+use cuid_util::to_base_36_padded;
+
 use crate::{BLOCK_SIZE, COUNTER, DISCRETE_VALUES};
 
 /// Fetch the counter value and increment it.
 ///
 /// If the counter has reached its max (DISCRETE VALUES), reset it to 0.
-fn fetch_and_increment() -> u32 {
+pub fn fetch_and_increment() -> u32 {
+    // `try_update()` is the new name, but is not available on older toolchains
+    #[allow(deprecated)]
     COUNTER
         .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |i| match i {
             i if i == DISCRETE_VALUES - 1 => Some(0),
@@ -20,7 +23,7 @@ fn fetch_and_increment() -> u32 {
 
 /// Return the current counter value in the appropriate base as a String.
 pub fn current() -> String {
-    pad(BLOCK_SIZE, to_base_string(fetch_and_increment()))
+    to_base_36_padded(fetch_and_increment(), BLOCK_SIZE)
 }
 
 #[cfg(test)]
@@ -0,0 +1,70 @@
+//! Diesel support for v1 CUIDs, enabled with the `diesel` feature.
+//!
+//! [`Cuid1`] may be used directly in Diesel models for `Text` columns, with
+//! any backend. IDs are stored as text, and loading fails for any value
+//! which is not a valid v1 CUID.
+
+use ::diesel::{
+    backend::Backend,
+    deserialize::{self, FromSql, Queryable},
+    expression::AsExpression,
+    serialize::{self, Output, ToSql},
+    sql_types::{Nullable, SingleValue, Text},
+};
+
+use crate::Cuid1;
+
+impl AsExpression<Text> for Cuid1 {
+    type Expression = <String as AsExpression<Text>>::Expression;
+
+    fn as_expression(self) -> Self::Expression {
+        AsExpression::<Text>::as_expression(self.into_string())
+    }
+}
+impl AsExpression<Nullable<Text>> for Cuid1 {
+    type Expression = <String as AsExpression<Nullable<Text>>>::Expression;
+
+    fn as_expression(self) -> Self::Expression {
+        AsExpression::<Nullable<Text>>::as_expression(self.into_string())
+    }
+}
+impl<'a> AsExpression<Text> for &'a Cuid1 {
+    type Expression = <&'a str as AsExpression<Text>>::Expression;
+
+    fn as_expression(self) -> Self::Expression {
+        AsExpression::<Text>::as_expression(self.as_str())
+    }
+}
+impl<'a> AsExpression<Nullable<Text>> for &'a Cuid1 {
+    type Expression = <&'a str as AsExpression<Nullable<Text>>>::Expression;
+
+    fn as_expression(self) -> Self::Expression {
+        AsExpression::<Nullable<Text>>::as_expression(self.as_str())
+    }
+}
+impl<DB: Backend> ToSql<Text, DB> for Cuid1
+where
+    str: ToSql<Text, DB>,
+{
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, DB>) -> serialize::Result {
+        self.as_str().to_sql(out)
+    }
+}
+impl<DB: Backend> FromSql<Text, DB> for Cuid1
+where
+    String: FromSql<Text, DB>,
+{
+    fn from_sql(bytes: DB::RawValue<'_>) -> deserialize::Result<Self> {
+        Ok(Self::try_from(String::from_sql(bytes)?)?)
+    }
+}
+impl<DB: Backend, ST: SingleValue> Queryable<ST, DB> for Cuid1
+where
+    Self: FromSql<ST, DB>,
+{
+    type Row = Self;
+
+    fn build(row: Self) -> deserialize::Result<Self> {
+        Ok(row)
+    }
+}
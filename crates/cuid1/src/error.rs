@@ -0,0 +1,83 @@
+use std::fmt;
+
+use cuid_util::ClockError;
+
+/// The error returned when a CUID cannot be generated.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Error {
+    /// The clock reported a time before the Unix epoch.
+    ClockBeforeEpoch,
+}
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ClockBeforeEpoch => f.write_str("clock time is before the Unix epoch"),
+        }
+    }
+}
+impl std::error::Error for Error {}
+impl From<ClockError> for Error {
+    fn from(_: ClockError) -> Self {
+        Self::ClockBeforeEpoch
+    }
+}
+
+/// The reason a string is not a valid v1 CUID, as returned by
+/// [`validate_cuid()`](crate::validate_cuid) and
+/// [`validate_slug()`](crate::validate_slug), or when parsing a
+/// [`Cuid1`](crate::Cuid1).
+///
+/// Lengths are measured in bytes, and positions in characters, starting from
+/// zero.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ValidationError {
+    /// The string is shorter than the minimum length.
+    TooShort { length: usize, min: usize },
+    /// The string is longer than the maximum length.
+    TooLong { length: usize, max: usize },
+    /// The string does not start with the `c` prefix.
+    InvalidPrefix(char),
+    /// A character is not a lowercase ASCII letter or digit.
+    InvalidChar { position: usize, character: char },
+    /// The timestamp has a leading zero, so it is outside of the range of
+    /// timestamps which could produce an ID of this length.
+    InvalidTimestamp,
+    /// The UUID does not hold a v1 CUID packed by [`Cuid1::to_uuid()`].
+    ///
+    /// [`Cuid1::to_uuid()`]: crate::Cuid1::to_uuid
+    InvalidUuid,
+}
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooShort { length, min } => {
+                write!(
+                    f,
+                    "CUID is too short: {length} bytes, expected at least {min}"
+                )
+            }
+            Self::TooLong { length, max } => {
+                write!(
+                    f,
+                    "CUID is too long: {length} bytes, expected at most {max}"
+                )
+            }
+            Self::InvalidPrefix(character) => {
+                write!(f, "CUID must start with 'c', found {character:?}")
+            }
+            Self::InvalidChar {
+                position,
+                character,
+            } => write!(
+                f,
+                "invalid character {character:?} at position {position}, expected a \
+                 lowercase letter or digit"
+            ),
+            Self::InvalidTimestamp => f.write_str("CUID timestamp has a leading zero"),
+            Self::InvalidUuid => f.write_str("UUID does not contain a packed CUID"),
+        }
+    }
+}
+impl std::error::Error for ValidationError {}
@@ -1,5 +1,6 @@
+use cuid_util::to_base_36_padded;
+
 use crate::BASE;
-use crate::text::{pad, to_base_string};
 
 static FINGERPRINT_PADDING: usize = 2;
 
@@ -12,7 +13,7 @@ fn pid() -> String {
     #[cfg(target_family = "wasm")]
     let pid = rand::random::<u32>();
 
-    pad(FINGERPRINT_PADDING, to_base_string(pid))
+    to_base_36_padded(pid, FINGERPRINT_PADDING)
 }
 
 /// Convert the hostname to a padded String in the appropriate base.
@@ -22,12 +23,10 @@ fn pid() -> String {
 /// the integer value of each character in the hostname, then converts that
 /// number to base radix.
 fn convert_hostname(hn: &str) -> String {
-    pad(
+    to_base_36_padded(
+        hn.chars()
+            .fold(hn.len() + BASE as usize, |acc, c| acc + c as usize) as u64,
         FINGERPRINT_PADDING,
-        to_base_string(
-            hn.chars()
-                .fold(hn.len() + BASE as usize, |acc, c| acc + c as usize) as u64,
-        ),
     )
 }
 
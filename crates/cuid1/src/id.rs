@@ -0,0 +1,455 @@
+use std::{borrow::Borrow, fmt, ops::Range, str::FromStr, time::Duration};
+
+use cuid_util::{from_base_36, to_base_36, to_base_36_padded};
+use uuid::Uuid;
+
+// std::time::SystemTime panics on WASM, so use a different library there.
+#[cfg(not(target_family = "wasm"))]
+use std::time::{SystemTime, UNIX_EPOCH};
+#[cfg(target_family = "wasm")]
+use web_time::{SystemTime, UNIX_EPOCH};
+
+use crate::{BASE, BLOCK_SIZE, START_STR, ValidationError, check_alphabet, cuid, validate_cuid};
+
+/// Number of characters following the timestamp: the counter, fingerprint,
+/// and two random blocks.
+const TAIL_LENGTH: usize = BLOCK_SIZE * 4;
+
+/// Number of bits used for each block in the UUID packing, enough for any
+/// block of 4 base 36 digits, since `36^4 < 2^21`.
+const UUID_BLOCK_BITS: u32 = 21;
+
+/// Number of bits following the timestamp in the UUID packing.
+const UUID_TAIL_BITS: u32 = UUID_BLOCK_BITS * 4;
+
+/// A v1 CUID, parsed into its component parts.
+///
+/// A `Cuid1` can only be obtained by generating a new ID or by parsing a
+/// string which has the structure of a v1 CUID.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::time::{Duration, SystemTime};
+///
+/// let id: cuid1::Cuid1 = cuid1::cuid().parse().unwrap();
+///
+/// // The timestamp is accurate to the millisecond
+/// let age = SystemTime::now().duration_since(id.timestamp()).unwrap();
+/// assert!(age < Duration::from_secs(1));
+///
+/// assert_eq!(4, id.fingerprint().len());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Cuid1(String);
+impl Cuid1 {
+    /// The length of the longest ID which can be converted to a `u128` or to
+    /// bytes. IDs generated before 2059, whose timestamps are 8 characters
+    /// long, are this length.
+    pub const MAX_BINARY_LENGTH: usize = 25;
+
+    /// Generate a new v1 CUID.
+    #[inline]
+    pub fn new() -> Self {
+        Self::from_string_unchecked(cuid())
+    }
+
+    /// Wraps a string which is known to be a valid v1 CUID, either because it
+    /// was just generated or because it has already been validated.
+    #[inline]
+    pub(crate) fn from_string_unchecked(id: String) -> Self {
+        debug_assert!(check_structure(&id).is_ok(), "invalid CUID {id}");
+        Self(id)
+    }
+
+    /// Returns the ID as a string slice.
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Consumes the ID, returning the inner `String`.
+    #[inline]
+    pub fn into_string(self) -> String {
+        self.0
+    }
+
+    /// Packs the ID into a `u128`, reading everything after the leading `c`
+    /// as a base 36 number, or returns `None` if it is longer than
+    /// [`MAX_BINARY_LENGTH`](Self::MAX_BINARY_LENGTH).
+    ///
+    /// Since timestamps never have leading zeros, the conversion is lossless,
+    /// and [`from_u128()`](Self::from_u128) restores the original ID.
+    ///
+    /// ```
+    /// use cuid1::Cuid1;
+    ///
+    /// let id = Cuid1::new();
+    /// let packed = id.to_u128().unwrap();
+    /// assert_eq!(id, Cuid1::from_u128(packed).unwrap());
+    /// ```
+    pub fn to_u128(&self) -> Option<u128> {
+        if self.0.len() > Self::MAX_BINARY_LENGTH {
+            return None;
+        }
+        // Panic safety: the ID was checked on construction to contain only
+        // base 36 digits after the prefix, and no more than 24 of them, which
+        // is always representable as a u128.
+        Some(
+            from_base_36(&self.0[START_STR.len()..])
+                .expect("CUID digits are validated on construction"),
+        )
+    }
+
+    /// Packs the ID into 16 big-endian bytes, suitable for a `BYTEA`, `BLOB`,
+    /// or `UUID` column, or returns `None` if it is longer than
+    /// [`MAX_BINARY_LENGTH`](Self::MAX_BINARY_LENGTH). See
+    /// [`to_u128()`](Self::to_u128).
+    pub fn to_bytes(&self) -> Option<[u8; 16]> {
+        self.to_u128().map(u128::to_be_bytes)
+    }
+
+    /// Unpacks an ID produced by [`to_u128()`](Self::to_u128), or returns an
+    /// error if the number is not a v1 CUID of at most
+    /// [`MAX_BINARY_LENGTH`](Self::MAX_BINARY_LENGTH) characters.
+    pub fn from_u128(number: u128) -> Result<Self, ValidationError> {
+        let id = format!("{START_STR}{}", to_base_36(number));
+        if id.len() > Self::MAX_BINARY_LENGTH {
+            return Err(ValidationError::TooLong {
+                length: id.len(),
+                max: Self::MAX_BINARY_LENGTH,
+            });
+        }
+        Self::try_from(id)
+    }
+
+    /// Unpacks an ID produced by [`to_bytes()`](Self::to_bytes). See
+    /// [`from_u128()`](Self::from_u128).
+    pub fn from_bytes(bytes: [u8; 16]) -> Result<Self, ValidationError> {
+        Self::from_u128(u128::from_be_bytes(bytes))
+    }
+
+    /// Packs the ID into a UUID, for storage in a `uuid` column, or returns
+    /// `None` if it is longer than [`MAX_BINARY_LENGTH`](Self::MAX_BINARY_LENGTH).
+    ///
+    /// The components of the ID are stored as numbers, from the most
+    /// significant bits to the least:
+    ///
+    /// | Bits | Contents |
+    /// |------|----------|
+    /// | 2    | Zero |
+    /// | 42   | [Timestamp](Self::timestamp_millis), in milliseconds |
+    /// | 21   | [Counter](Self::counter) |
+    /// | 21   | [Fingerprint](Self::fingerprint) |
+    /// | 42   | [Random blocks](Self::random_blocks), 21 bits each |
+    ///
+    /// UUIDs therefore sort in the same order as the IDs they hold, keeping
+    /// the locality of sequential IDs in an index, and the timestamp may be
+    /// extracted by shifting the UUID right by 84 bits. The UUIDs do not have
+    /// a standard version or variant. [`from_uuid()`](Self::from_uuid)
+    /// restores the original ID.
+    ///
+    /// ```
+    /// use cuid1::Cuid1;
+    ///
+    /// let id = Cuid1::new();
+    /// let uuid = id.to_uuid().unwrap();
+    /// assert_eq!(u128::from(id.timestamp_millis()), uuid.as_u128() >> 84);
+    /// assert_eq!(id, Cuid1::from_uuid(uuid).unwrap());
+    /// ```
+    pub fn to_uuid(&self) -> Option<Uuid> {
+        if self.0.len() > Self::MAX_BINARY_LENGTH {
+            return None;
+        }
+        let packed = (0..4).fold(u128::from(self.timestamp_millis()), |packed, idx| {
+            // Panic safety: blocks were checked on construction to contain 4
+            // base 36 digits.
+            let block =
+                from_base_36(self.block(idx)).expect("blocks are validated on construction");
+            (packed << UUID_BLOCK_BITS) | block
+        });
+        Some(Uuid::from_u128(packed))
+    }
+
+    /// Unpacks an ID produced by [`to_uuid()`](Self::to_uuid), or returns
+    /// [`ValidationError::InvalidUuid`] if the UUID does not hold a v1 CUID.
+    pub fn from_uuid(uuid: Uuid) -> Result<Self, ValidationError> {
+        const BLOCK_MASK: u128 = (1 << UUID_BLOCK_BITS) - 1;
+        // Timestamps are 8 digits long in IDs which fit in a UUID, with no
+        // leading zero
+        const TIMESTAMPS: Range<u128> = 36_u128.pow(7)..36_u128.pow(8);
+        const BLOCKS: Range<u128> = 0..36_u128.pow(BLOCK_SIZE as u32);
+
+        let packed = uuid.as_u128();
+        let timestamp = packed >> UUID_TAIL_BITS;
+        if !TIMESTAMPS.contains(&timestamp) {
+            return Err(ValidationError::InvalidUuid);
+        }
+
+        let mut id = String::with_capacity(Self::MAX_BINARY_LENGTH);
+        id.push_str(START_STR);
+        id.push_str(&to_base_36(timestamp));
+        for idx in (0..4).rev() {
+            let block = (packed >> (idx * UUID_BLOCK_BITS)) & BLOCK_MASK;
+            if !BLOCKS.contains(&block) {
+                return Err(ValidationError::InvalidUuid);
+            }
+            id.push_str(&to_base_36_padded(block, BLOCK_SIZE));
+        }
+        Ok(Self::from_string_unchecked(id))
+    }
+
+    /// The time at which the ID was generated, with millisecond precision.
+    pub fn timestamp(&self) -> SystemTime {
+        UNIX_EPOCH + Duration::from_millis(self.timestamp_millis())
+    }
+
+    /// The time at which the ID was generated, in milliseconds since the
+    /// Unix epoch.
+    pub fn timestamp_millis(&self) -> u64 {
+        // Panic safety: the timestamp block was checked on construction to
+        // contain only base 36 digits, and is no more than 9 characters long,
+        // which is always representable as a u64.
+        u64::from_str_radix(self.timestamp_block(), BASE.into())
+            .expect("timestamp is validated on construction")
+    }
+
+    /// The value of the counter at the time of generation.
+    pub fn counter(&self) -> u32 {
+        // Panic safety: the counter block was checked on construction to
+        // contain 4 base 36 digits.
+        u32::from_str_radix(self.block(0), BASE.into())
+            .expect("counter is validated on construction")
+    }
+
+    /// The fingerprint of the host and process which generated the ID.
+    pub fn fingerprint(&self) -> &str {
+        self.block(1)
+    }
+
+    /// The two blocks of random data at the end of the ID.
+    pub fn random_blocks(&self) -> [&str; 2] {
+        [self.block(2), self.block(3)]
+    }
+
+    fn timestamp_block(&self) -> &str {
+        &self.0[START_STR.len()..self.0.len() - TAIL_LENGTH]
+    }
+
+    /// Retrieve one of the fixed-size blocks following the timestamp.
+    fn block(&self, idx: usize) -> &str {
+        let start = self.0.len() - TAIL_LENGTH + idx * BLOCK_SIZE;
+        &self.0[start..start + BLOCK_SIZE]
+    }
+}
+impl Default for Cuid1 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl fmt::Display for Cuid1 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+impl AsRef<str> for Cuid1 {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+impl Borrow<str> for Cuid1 {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+impl From<Cuid1> for String {
+    fn from(id: Cuid1) -> Self {
+        id.0
+    }
+}
+impl FromStr for Cuid1 {
+    type Err = ValidationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from(s)
+    }
+}
+impl TryFrom<&str> for Cuid1 {
+    type Error = ValidationError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        check_structure(value)?;
+        Ok(Self::from_string_unchecked(value.to_owned()))
+    }
+}
+impl TryFrom<String> for Cuid1 {
+    type Error = ValidationError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        check_structure(&value)?;
+        Ok(Self::from_string_unchecked(value))
+    }
+}
+
+/// Ensure that the string can be broken down into its component parts.
+pub(crate) fn check_structure(to_check: &str) -> Result<(), ValidationError> {
+    validate_cuid(to_check)?;
+    check_alphabet(to_check)?;
+
+    // Index safety: the string is all ASCII, and at least 25 characters long.
+    let timestamp = &to_check[START_STR.len()..to_check.len() - TAIL_LENGTH];
+    // Timestamps are encoded without leading zeros, so a leading zero means
+    // that the timestamp is shorter, and earlier, than the length implies.
+    if timestamp.starts_with('0') {
+        return Err(ValidationError::InvalidTimestamp);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod id_tests {
+    use super::*;
+
+    #[test]
+    fn parse_components() {
+        // Example ID from the README of the original javascript implementation
+        let id: Cuid1 = "cjld2cjxh0000qzrmn831i7rn".parse().unwrap();
+        assert_eq!(1535421552101, id.timestamp_millis());
+        assert_eq!(
+            UNIX_EPOCH + Duration::from_millis(1535421552101),
+            id.timestamp()
+        );
+        assert_eq!(0, id.counter());
+        assert_eq!("qzrm", id.fingerprint());
+        assert_eq!(["n831", "i7rn"], id.random_blocks());
+    }
+
+    #[test]
+    fn parse_leading_zero_timestamp() {
+        assert_eq!(
+            Err(ValidationError::InvalidTimestamp),
+            "c0ld2cjxh0000qzrmn831i7rn".parse::<Cuid1>()
+        );
+    }
+
+    #[test]
+    fn generated_round_trip() {
+        let id = Cuid1::new();
+        assert_eq!(id, id.as_str().parse().unwrap());
+        assert_eq!(id, Cuid1::try_from(id.clone().into_string()).unwrap());
+    }
+
+    #[test]
+    fn binary_round_trip() {
+        let id = Cuid1::new();
+        let packed = id.to_u128().unwrap();
+        assert_eq!(id, Cuid1::from_u128(packed).unwrap());
+        assert_eq!(packed.to_be_bytes(), id.to_bytes().unwrap());
+        assert_eq!(id, Cuid1::from_bytes(id.to_bytes().unwrap()).unwrap());
+
+        let id: Cuid1 = "cjld2cjxh0000qzrmn831i7rn".parse().unwrap();
+        assert_eq!(id, Cuid1::from_u128(id.to_u128().unwrap()).unwrap());
+    }
+
+    #[test]
+    fn binary_invalid() {
+        let long: Cuid1 = "c1jld2cjxh0000qzrmn831i7rn".parse().unwrap();
+        assert_eq!(None, long.to_u128());
+        assert_eq!(None, long.to_bytes());
+        assert_eq!(
+            Err(ValidationError::TooShort { length: 2, min: 25 }),
+            Cuid1::from_u128(1)
+        );
+        assert_eq!(
+            Err(ValidationError::TooLong {
+                length: 26,
+                max: 25
+            }),
+            Cuid1::from_bytes([255; 16])
+        );
+    }
+
+    #[test]
+    fn uuid_round_trip() {
+        let id = Cuid1::new();
+        let uuid = id.to_uuid().unwrap();
+        assert_eq!(id, Cuid1::from_uuid(uuid).unwrap());
+        assert_eq!(u128::from(id.timestamp_millis()), uuid.as_u128() >> 84);
+
+        let id: Cuid1 = "cjld2cjxh0000qzrmn831i7rn".parse().unwrap();
+        let uuid = id.to_uuid().unwrap();
+        assert_eq!(id, Cuid1::from_uuid(uuid).unwrap());
+        assert_eq!(0, uuid.as_u128() >> 126);
+        assert_eq!(1535421552101, uuid.as_u128() >> 84);
+    }
+
+    #[test]
+    fn uuid_order() {
+        let ids = [
+            "cjld2cjxh0000qzrmn831i7rn",
+            "cjld2cjxh0001qzrmn831i7rn",
+            "cjld2cjxh0001qzrn0000i7rn",
+            "cjld2cjxi0000aaaa00000000",
+            "czzzzzzzzzzzzzzzzzzzzzzzz",
+        ]
+        .map(|id| id.parse::<Cuid1>().unwrap());
+        let uuids = ids.clone().map(|id| id.to_uuid().unwrap());
+        assert!(uuids.is_sorted());
+        // Postgres compares UUIDs bytewise
+        assert!(uuids.map(|uuid| uuid.into_bytes()).is_sorted());
+        assert_eq!(ids, uuids.map(|uuid| Cuid1::from_uuid(uuid).unwrap()));
+    }
+
+    #[test]
+    fn uuid_invalid() {
+        let long: Cuid1 = "c1jld2cjxh0000qzrmn831i7rn".parse().unwrap();
+        assert_eq!(None, long.to_uuid());
+        for uuid in [
+            Uuid::nil(),
+            Uuid::from_u128(u128::MAX),
+            // A timestamp with a leading zero
+            Uuid::from_u128(1 << UUID_TAIL_BITS),
+            // A block which is too large
+            Uuid::from_u128((36_u128.pow(7) << UUID_TAIL_BITS) | ((1 << UUID_BLOCK_BITS) - 1)),
+        ] {
+            assert_eq!(Err(ValidationError::InvalidUuid), Cuid1::from_uuid(uuid));
+        }
+    }
+
+    #[test]
+    fn timestamp_is_recent() {
+        let id = Cuid1::new();
+        let age = SystemTime::now().duration_since(id.timestamp()).unwrap();
+        assert!(age < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn parse_invalid() {
+        assert_eq!(
+            Err(ValidationError::TooShort { length: 0, min: 25 }),
+            "".parse::<Cuid1>()
+        );
+        assert_eq!(
+            Err(ValidationError::InvalidChar {
+                position: 1,
+                character: '!'
+            }),
+            "c!!!!!!!!!!!!!!!!!!!!!!!!".parse::<Cuid1>()
+        );
+        assert_eq!(
+            Err(ValidationError::InvalidPrefix('x')),
+            "xjld2cjxh0000qzrmn831i7rn".parse::<Cuid1>()
+        );
+        assert_eq!(
+            Err(ValidationError::InvalidChar {
+                position: 1,
+                character: 'J'
+            }),
+            "cJLD2CJXH0000QZRMN831I7RN".parse::<Cuid1>()
+        );
+        assert_eq!(
+            Err(ValidationError::InvalidPrefix('é')),
+            "éjld2cjxh0000qzrmn831i7r".parse::<Cuid1>()
+        );
+    }
+}
@@ -1,24 +1,73 @@
 //! CUID version one
+//!
+//! Generate IDs with [`cuid()`] or [`slug()`]. Full IDs may be parsed into a
+//! [`Cuid1`] to inspect their component parts:
+//!
+//! ```rust
+//! let id: cuid1::Cuid1 = cuid1::cuid().parse().unwrap();
+//! println!("created at {:?} on host {}", id.timestamp(), id.fingerprint());
+//! ```
+//!
+//! ## Features
+//!
+//! - `serde`: implements `Serialize` and `Deserialize` for [`Cuid1`], and
+//!   provides helpers for validating `String` fields. See the `serde` module.
+//! - `sqlx`: implements sqlx's `Type`, `Encode`, and `Decode` for [`Cuid1`],
+//!   storing it as text in any database which supports strings. Decoding
+//!   fails for invalid IDs.
+//! - `diesel`: lets [`Cuid1`] be used in Diesel models for `Text` columns,
+//!   failing to load invalid IDs.
+//! - `rusqlite`: implements rusqlite's `ToSql` and `FromSql` for [`Cuid1`],
+//!   storing it as text. Reading fails for invalid IDs. Also provides a
+//!   `cuid()` SQLite function for generating IDs. See the `rusqlite` module.
 
 mod counter;
+#[cfg(feature = "diesel")]
+mod diesel;
+mod error;
 mod fingerprint;
+mod id;
 mod random;
+#[cfg(feature = "rusqlite")]
+pub mod rusqlite;
+#[cfg(feature = "serde")]
+pub mod serde;
+#[cfg(feature = "sqlx")]
+mod sqlx;
 mod text;
 mod time;
 
 use std::sync::{
-    LazyLock,
+    LazyLock, PoisonError, RwLock, RwLockReadGuard,
     atomic::{AtomicU32, Ordering},
 };
 
+use cuid_util::to_base_36_padded;
+
 const BASE: u8 = 36;
 const BLOCK_SIZE: usize = 4;
 const DISCRETE_VALUES: u32 = 1679616; // BASE^BLOCK_SIZE
 const START_STR: &str = "c";
 
+pub use cuid_util::{Clock, ClockError, InlineId, SystemClock};
+pub use error::{Error, ValidationError};
+pub use id::Cuid1;
+
 static COUNTER: AtomicU32 = AtomicU32::new(0);
 
-static FINGERPRINT: LazyLock<String> = LazyLock::new(fingerprint::fingerprint);
+/// The host fingerprint, which includes the process ID. Regenerated by
+/// [`reseed()`], since a forked child process would otherwise keep its
+/// parent's.
+static FINGERPRINT: LazyLock<RwLock<String>> =
+    LazyLock::new(|| RwLock::new(fingerprint::fingerprint()));
+
+/// Retrieve the current fingerprint.
+///
+/// The lock is only ever held to read or replace the string, so a poisoned
+/// lock still contains a valid fingerprint.
+fn current_fingerprint() -> RwLockReadGuard<'static, String> {
+    FINGERPRINT.read().unwrap_or_else(PoisonError::into_inner)
+}
 
 /// Generate a v1 CUID
 ///
@@ -31,6 +80,10 @@ static FINGERPRINT: LazyLock<String> = LazyLock::new(fingerprint::fingerprint);
 /// - A (base 36) fingerprint derived from the system's hostname
 /// - Two random numbers between 0 and 36^4, each converted to base 36
 ///
+/// A clock set with [`set_clock()`] which reports a time before 1972 or after
+/// the year 5188 is clamped to the nearest time that encodes to a valid
+/// timestamp of 8 or 9 characters.
+///
 /// # Examples
 ///
 /// ```rust
@@ -43,13 +96,75 @@ pub fn cuid() -> String {
         START_STR,
         &time::timestamp(),
         &counter::current(),
-        &FINGERPRINT,
+        &current_fingerprint(),
         &random::random_block(),
         &random::random_block(),
     ]
     .concat()
 }
 
+/// Generate a v1 CUID, or return an error if one cannot be generated.
+///
+/// Unlike [`cuid()`], which panics if the clock reports a time before the
+/// Unix epoch, this never panics.
+///
+/// # Examples
+///
+/// ```rust
+/// let id = cuid1::try_cuid().unwrap();
+/// assert!(cuid1::is_cuid(id));
+/// ```
+#[inline]
+pub fn try_cuid() -> Result<String, Error> {
+    Ok([
+        START_STR,
+        &time::try_timestamp()?,
+        &counter::current(),
+        &current_fingerprint(),
+        &random::random_block(),
+        &random::random_block(),
+    ]
+    .concat())
+}
+
+/// Generate a v1 CUID, stored inline rather than on the heap.
+///
+/// This produces the same IDs as [`cuid()`], but without allocating.
+///
+/// # Examples
+///
+/// ```rust
+/// let id = cuid1::cuid_inline();
+/// assert!(cuid1::is_cuid(id));
+/// ```
+#[inline]
+pub fn cuid_inline() -> InlineId {
+    try_cuid_inline().unwrap_or_else(|err| panic!("Failed to create CUID: {err}"))
+}
+
+/// Generate a v1 CUID stored inline, or return an error if one cannot be
+/// generated.
+///
+/// Unlike [`cuid_inline()`], this never panics.
+///
+/// # Examples
+///
+/// ```rust
+/// let id = cuid1::try_cuid_inline().unwrap();
+/// assert!(cuid1::is_cuid(id));
+/// ```
+#[inline]
+pub fn try_cuid_inline() -> Result<InlineId, Error> {
+    let mut id = InlineId::new();
+    id.push_str(START_STR);
+    id.push_base_36(time::clamp_millis(time::try_timestamp_millis()?));
+    id.push_base_36_padded(counter::fetch_and_increment(), BLOCK_SIZE);
+    id.push_str(&current_fingerprint());
+    id.push_base_36_padded(random::random_block_value(), BLOCK_SIZE);
+    id.push_base_36_padded(random::random_block_value(), BLOCK_SIZE);
+    Ok(id)
+}
+
 /// Generate a v1 CUID.
 ///
 /// Alias for [`cuid`].
@@ -100,17 +215,34 @@ pub fn one_off_cuid1() -> String {
 /// ```
 #[inline]
 pub fn slug() -> String {
-    let timestamp = time::timestamp();
+    try_slug().unwrap_or_else(|err| panic!("Failed to create CUID slug: {err}"))
+}
+
+/// Generate a CUID v1 slug, or return an error if one cannot be generated.
+///
+/// Unlike [`slug()`], this never panics.
+///
+/// # Examples
+///
+/// ```rust
+/// let slug = cuid1::try_slug().unwrap();
+/// assert!(cuid1::is_slug(slug));
+/// ```
+#[inline]
+pub fn try_slug() -> Result<String, Error> {
+    // Pad the timestamp, in case the clock reports a time close to the epoch
+    let timestamp = to_base_36_padded(time::try_timestamp_millis()?, 2);
     let count = counter::current();
     let rand = random::random_block();
-    [
-        &timestamp[timestamp.len() - 2..],
+    let fingerprint = current_fingerprint();
+    Ok([
+        &timestamp,
         &count[count.len().saturating_sub(4)..],
-        &FINGERPRINT[..1],
-        &FINGERPRINT[FINGERPRINT.len() - 1..],
+        &fingerprint[..1],
+        &fingerprint[fingerprint.len() - 1..],
         &rand[rand.len() - 2..],
     ]
-    .concat()
+    .concat())
 }
 
 /// Generate a single CUID slug, for use in the cuid binary.
@@ -125,6 +257,61 @@ pub fn one_off_cuid1_slug() -> String {
     slug()
 }
 
+/// Reinitialize the fingerprint, counter, and random number generator.
+///
+/// The fingerprint includes the process ID, but it is computed only once, and
+/// like the counter and the thread-local RNG, it is copied as-is into a child
+/// process by `fork()`. Without reseeding, a parent and its children (or
+/// several children of the same parent) may generate colliding IDs. Call this
+/// in the child immediately after forking, e.g. in a pre-fork server's worker
+/// initialization hook.
+///
+/// Reseeding also moves the counter to a random starting point, so IDs from
+/// sibling processes do not share counter values.
+///
+/// # Panics
+///
+/// Panics if the operating system's random number source fails.
+///
+/// # Examples
+///
+/// ```rust
+/// cuid1::reseed();
+/// assert!(cuid1::is_cuid(cuid1::cuid()));
+/// ```
+pub fn reseed() {
+    rand::rng()
+        .reseed()
+        .expect("failed to reseed the thread-local RNG");
+    *FINGERPRINT.write().unwrap_or_else(PoisonError::into_inner) = fingerprint::fingerprint();
+    COUNTER.store(rand::random_range(0..DISCRETE_VALUES), Ordering::Relaxed);
+}
+
+/// Use the specified clock to timestamp all v1 CUIDs and slugs generated by
+/// this process, rather than the [`SystemClock`].
+///
+/// This is intended for testing and simulation. Since it affects the whole
+/// process, tests which set a clock should be run in their own test binary,
+/// or with `--test-threads=1`.
+///
+/// # Examples
+///
+/// ```rust
+/// cuid1::set_clock(|| 1_535_421_552_101);
+/// let id: cuid1::Cuid1 = cuid1::cuid().parse().unwrap();
+/// assert_eq!(1_535_421_552_101, id.timestamp_millis());
+///
+/// cuid1::reset_clock();
+/// ```
+pub fn set_clock(clock: impl Clock + 'static) {
+    time::set_clock(Some(Box::new(clock)));
+}
+
+/// Go back to using the [`SystemClock`] after a call to [`set_clock()`].
+pub fn reset_clock() {
+    time::set_clock(None);
+}
+
 /// Return whether a string looks like it could be a legitimate CUID
 ///
 /// # Examples
@@ -135,20 +322,89 @@ pub fn one_off_cuid1_slug() -> String {
 /// ```
 #[inline]
 pub fn is_cuid<S: AsRef<str>>(to_check: S) -> bool {
+    validate_cuid(to_check).is_ok()
+}
+
+/// Check whether a string looks like it could be a legitimate CUID, returning
+/// the reason if not.
+///
+/// Like [`is_cuid()`], this only checks the length and prefix of the string.
+///
+/// # Examples
+///
+/// ```rust
+/// use cuid1::ValidationError;
+///
+/// assert_eq!(Ok(()), cuid1::validate_cuid(cuid1::cuid()));
+/// assert_eq!(
+///     Err(ValidationError::InvalidPrefix('x')),
+///     cuid1::validate_cuid("xjld2cjxh0000qzrmn831i7rn")
+/// );
+/// ```
+#[inline]
+pub fn validate_cuid<S: AsRef<str>>(to_check: S) -> Result<(), ValidationError> {
+    // the CUID length will increase as the timestamp increases. The
+    // timestamp portion currently represents 8 characters. It has the
+    // potential to increase to up to 15 characters when the timestamp
+    // reaches the maximum 64-bit integer, at which point the earth will be
+    // long gone, presumably along with this code. At that time, the CUID
+    // length would be 32. 9 characters gives us up through at least the
+    // year 5138, though, so checking for 25 or 26 characters should do it.
+    const MIN_LENGTH: usize = 25;
+    const MAX_LENGTH: usize = 26;
+
     let to_check = to_check.as_ref();
-    match to_check.len() {
-        // the CUID length will increase as the timestamp increases. The
-        // timestamp portion currently represents 8 characters. It has the
-        // potential to increase to up to 15 characters when the timestamp
-        // reaches the maximum 64-bit integer, at which point the earth will be
-        // long gone, presumably along with this code. At that time, the CUID
-        // length would be 32. 9 characters gives us up through at least the
-        // year 5138, though, so checking for 25 or 26 characters should do it.
-        25..=26 => &to_check[..1] == START_STR,
-        _ => false,
+    let length = to_check.len();
+    if length < MIN_LENGTH {
+        return Err(ValidationError::TooShort {
+            length,
+            min: MIN_LENGTH,
+        });
+    }
+    if length > MAX_LENGTH {
+        return Err(ValidationError::TooLong {
+            length,
+            max: MAX_LENGTH,
+        });
+    }
+    match to_check.chars().next() {
+        Some(first) if !START_STR.starts_with(first) => Err(ValidationError::InvalidPrefix(first)),
+        _ => Ok(()),
     }
 }
 
+/// Return whether a string has the full structure of a v1 CUID.
+///
+/// Unlike [`is_cuid()`], this checks that every character is a lowercase
+/// base 36 digit, and that the timestamp is encoded without leading zeros,
+/// which places it between mid-1972 and the year 5138. The counter,
+/// fingerprint, and random blocks are then always exactly four base 36 digits
+/// each. A string which passes this check can always be parsed into a
+/// [`Cuid1`].
+///
+/// # Examples
+///
+/// ```rust
+/// assert!(cuid1::is_cuid_strict(cuid1::cuid()));
+///
+/// // Passes the permissive check, but not the strict one
+/// assert!(cuid1::is_cuid("c!!!!!!!!!!!!!!!!!!!!!!!!"));
+/// assert!(!cuid1::is_cuid_strict("c!!!!!!!!!!!!!!!!!!!!!!!!"));
+/// ```
+#[inline]
+pub fn is_cuid_strict<S: AsRef<str>>(to_check: S) -> bool {
+    validate_cuid_strict(to_check).is_ok()
+}
+
+/// Check whether a string has the full structure of a v1 CUID, returning the
+/// reason if not.
+///
+/// See [`is_cuid_strict()`] for the checks performed.
+#[inline]
+pub fn validate_cuid_strict<S: AsRef<str>>(to_check: S) -> Result<(), ValidationError> {
+    id::check_structure(to_check.as_ref())
+}
+
 /// Return whether a string looks like it could be a legitimate v1 CUID slug
 ///
 /// # Examples
@@ -159,8 +415,74 @@ pub fn is_cuid<S: AsRef<str>>(to_check: S) -> bool {
 /// ```
 #[inline]
 pub fn is_slug<S: AsRef<str>>(to_check: S) -> bool {
+    validate_slug(to_check).is_ok()
+}
+
+/// Check whether a string looks like it could be a legitimate v1 CUID slug,
+/// returning the reason if not.
+///
+/// Like [`is_slug()`], this only checks the length of the string.
+#[inline]
+pub fn validate_slug<S: AsRef<str>>(to_check: S) -> Result<(), ValidationError> {
     // the slug will always be 10 characters
-    to_check.as_ref().len() == 10
+    const LENGTH: usize = 10;
+
+    let length = to_check.as_ref().len();
+    if length < LENGTH {
+        Err(ValidationError::TooShort {
+            length,
+            min: LENGTH,
+        })
+    } else if length > LENGTH {
+        Err(ValidationError::TooLong {
+            length,
+            max: LENGTH,
+        })
+    } else {
+        Ok(())
+    }
+}
+
+/// Return whether a string has the structure of a v1 CUID slug.
+///
+/// Unlike [`is_slug()`], this checks that every character is a lowercase
+/// base 36 digit.
+///
+/// # Examples
+///
+/// ```rust
+/// assert!(cuid1::is_slug_strict(cuid1::slug()));
+/// assert!(!cuid1::is_slug_strict("not a slug"));
+/// ```
+#[inline]
+pub fn is_slug_strict<S: AsRef<str>>(to_check: S) -> bool {
+    validate_slug_strict(to_check).is_ok()
+}
+
+/// Check whether a string has the structure of a v1 CUID slug, returning the
+/// reason if not.
+///
+/// See [`is_slug_strict()`] for the checks performed.
+#[inline]
+pub fn validate_slug_strict<S: AsRef<str>>(to_check: S) -> Result<(), ValidationError> {
+    let to_check = to_check.as_ref();
+    validate_slug(to_check)?;
+    check_alphabet(to_check)
+}
+
+/// Ensure that every character is a lowercase base 36 digit.
+fn check_alphabet(to_check: &str) -> Result<(), ValidationError> {
+    match to_check
+        .chars()
+        .enumerate()
+        .find(|(_, c)| !matches!(c, '0'..='9' | 'a'..='z'))
+    {
+        Some((position, character)) => Err(ValidationError::InvalidChar {
+            position,
+            character,
+        }),
+        None => Ok(()),
+    }
 }
 
 #[cfg(test)]
@@ -197,12 +519,83 @@ mod tests {
     }
     wasm_test!(cuid_is_cuid);
 
+    #[test]
+    fn cuid_inline_is_cuid() {
+        let id = cuid_inline();
+        assert_eq!(25, id.len());
+        assert!(is_cuid(id));
+    }
+    wasm_test!(cuid_inline_is_cuid);
+
+    #[test]
+    fn reseeding() {
+        reseed();
+        assert_eq!(4, current_fingerprint().len());
+        assert!(is_cuid_strict(cuid()));
+        assert!(is_slug_strict(slug()));
+    }
+    wasm_test!(reseeding);
+
+    #[test]
+    fn try_cuid_is_cuid() {
+        assert!(is_cuid(try_cuid().unwrap()));
+    }
+    wasm_test!(try_cuid_is_cuid);
+
     #[test]
     fn cuid_is_not_cuid_zero_len() {
         assert!(!is_cuid(""));
     }
     wasm_test!(cuid_is_not_cuid_zero_len);
 
+    #[test]
+    fn validation_reasons() {
+        assert_eq!(Ok(()), validate_cuid(cuid()));
+        assert_eq!(Ok(()), validate_slug(slug()));
+        assert_eq!(
+            Err(ValidationError::TooLong {
+                length: 27,
+                max: 26
+            }),
+            validate_cuid("c".repeat(27))
+        );
+        assert_eq!(
+            Err(ValidationError::InvalidPrefix('é')),
+            validate_cuid("é".repeat(13))
+        );
+        assert_eq!(
+            Err(ValidationError::TooShort { length: 9, min: 10 }),
+            validate_slug("a".repeat(9))
+        );
+        assert_eq!(
+            "CUID must start with 'c', found 'x'",
+            validate_cuid("x".repeat(25)).unwrap_err().to_string()
+        );
+    }
+    wasm_test!(validation_reasons);
+
+    #[test]
+    fn strict_validation() {
+        assert!(is_cuid_strict(cuid()));
+        assert!(is_cuid_strict(cuid_inline()));
+        assert!(is_cuid_strict("cjld2cjxh0000qzrmn831i7rn"));
+        assert!(is_slug_strict(slug()));
+
+        assert!(!is_cuid_strict("c!!!!!!!!!!!!!!!!!!!!!!!!"));
+        assert!(!is_cuid_strict("cjld2cjxh0000qzrmn831i7rN"));
+        assert!(!is_cuid_strict("c0ld2cjxh0000qzrmn831i7rn"));
+        assert!(!is_cuid_strict("xjld2cjxh0000qzrmn831i7rn"));
+        assert!(!is_slug_strict("!!!!!!!!!!"));
+        assert_eq!(
+            Err(ValidationError::InvalidChar {
+                position: 9,
+                character: ' '
+            }),
+            validate_slug_strict("abcdefghi ")
+        );
+    }
+    wasm_test!(strict_validation);
+
     #[test]
     fn slug_len() {
         assert!(slug().len() == 10);
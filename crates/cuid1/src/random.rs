@@ -1,7 +1,7 @@
+use cuid_util::to_base_36_padded;
 use rand::prelude::RngExt;
 
 use super::{BLOCK_SIZE, DISCRETE_VALUES};
-use crate::text::{pad, to_base_string};
 
 fn random_float_from_rng<R: RngExt>(mut rng: R) -> f64 {
     rng.random::<f64>()
@@ -15,11 +15,13 @@ fn random_64_bit_int<N: Into<f64>>(max: N) -> u64 {
     (random_float() * max.into()) as u64
 }
 
+/// Generate a random number that will fit into a block.
+pub fn random_block_value() -> u64 {
+    random_64_bit_int(DISCRETE_VALUES)
+}
+
 pub fn random_block() -> String {
-    pad(
-        BLOCK_SIZE,
-        to_base_string(random_64_bit_int(DISCRETE_VALUES)),
-    )
+    to_base_36_padded(random_block_value(), BLOCK_SIZE)
 }
 
 #[cfg(test)]
@@ -0,0 +1,82 @@
+//! rusqlite support for v1 CUIDs, enabled with the `rusqlite` feature.
+//!
+//! [`Cuid1`] implements `ToSql` and `FromSql`, storing IDs as text, and
+//! failing to read any value which is not a valid v1 CUID.
+//!
+//! [`register_function()`] adds a `cuid()` SQL function to a connection,
+//! which may be used as a column default:
+//!
+//! ```
+//! use cuid1::Cuid1;
+//! use rusqlite::Connection;
+//!
+//! let conn = Connection::open_in_memory()?;
+//! cuid1::rusqlite::register_function(&conn)?;
+//! conn.execute_batch(
+//!     "CREATE TABLE users (id TEXT PRIMARY KEY DEFAULT (cuid()), name TEXT);
+//!      INSERT INTO users (name) VALUES ('alice');",
+//! )?;
+//!
+//! let id: Cuid1 = conn.query_row("SELECT id FROM users", [], |row| row.get(0))?;
+//! # Ok::<(), rusqlite::Error>(())
+//! ```
+//!
+//! SQLite does not store functions in the database, so they must be
+//! registered on every connection which inserts rows relying on the default.
+
+use ::rusqlite::{
+    Connection, Error, Result,
+    functions::FunctionFlags,
+    types::{FromSql, FromSqlError, FromSqlResult, ToSql, ToSqlOutput, ValueRef},
+};
+
+use crate::{Cuid1, try_cuid};
+
+impl ToSql for Cuid1 {
+    fn to_sql(&self) -> Result<ToSqlOutput<'_>> {
+        Ok(self.as_str().into())
+    }
+}
+impl FromSql for Cuid1 {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        Self::try_from(value.as_str()?).map_err(|err| FromSqlError::Other(Box::new(err)))
+    }
+}
+
+/// Registers a `cuid()` SQL function on the connection, which generates a
+/// v1 CUID each time it is called.
+pub fn register_function(conn: &Connection) -> Result<()> {
+    conn.create_scalar_function(
+        "cuid",
+        0,
+        // Not deterministic, so that SQLite calls the function for every row,
+        // but innocuous, so that it may be used in the schema
+        FunctionFlags::SQLITE_UTF8 | FunctionFlags::SQLITE_INNOCUOUS,
+        |_| try_cuid().map_err(|err| Error::UserFunctionError(Box::new(err))),
+    )
+}
+
+#[cfg(all(test, not(target_family = "wasm")))]
+mod test {
+    use super::*;
+
+    #[test]
+    fn default_value() {
+        let conn = Connection::open_in_memory().unwrap();
+        register_function(&conn).unwrap();
+        conn.execute_batch(
+            "CREATE TABLE users (id TEXT PRIMARY KEY DEFAULT (cuid()), name TEXT);
+             INSERT INTO users (name) VALUES ('alice'), ('bob');",
+        )
+        .unwrap();
+
+        let mut stmt = conn.prepare("SELECT id FROM users").unwrap();
+        let ids = stmt
+            .query_map([], |row| row.get::<_, Cuid1>(0))
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(2, ids.len());
+        assert_ne!(ids[0], ids[1]);
+    }
+}
@@ -0,0 +1,149 @@
+//! Serde support for v1 CUIDs, enabled with the `serde` feature.
+//!
+//! [`Cuid1`] implements `Serialize` and `Deserialize` directly, serializing
+//! as a string and failing to deserialize any string which is not a valid
+//! v1 CUID.
+//!
+//! For existing `String` fields, the modules in this module may be used with
+//! `#[serde(with = "...")]` to get the same validation without changing the
+//! type of the field:
+//!
+//! ```
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct User {
+//!     #[serde(with = "cuid1::serde::string")]
+//!     id: String,
+//!     #[serde(with = "cuid1::serde::slug")]
+//!     short_id: String,
+//! }
+//!
+//! let user: Result<User, _> = serde_json::from_str(r#"{"id": "nope!", "short_id": "a1b2c3"}"#);
+//! assert!(user.is_err());
+//! ```
+
+use ::serde::{Deserialize, Deserializer, Serialize, Serializer, de};
+
+use crate::{Cuid1, is_cuid, is_slug, validate_cuid_strict};
+
+impl Serialize for Cuid1 {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+impl<'de> Deserialize<'de> for Cuid1 {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize_checked(
+            deserializer,
+            |value| validate_cuid_strict(value).is_ok(),
+            "a v1 CUID",
+        )
+        .map(Cuid1::from_string_unchecked)
+    }
+}
+
+/// Deserialize a string, failing if it does not pass `check`.
+fn deserialize_checked<'de, D: Deserializer<'de>>(
+    deserializer: D,
+    check: fn(&str) -> bool,
+    expected: &'static str,
+) -> Result<String, D::Error> {
+    let value = String::deserialize(deserializer)?;
+    if check(&value) {
+        Ok(value)
+    } else {
+        Err(de::Error::invalid_value(
+            de::Unexpected::Str(&value),
+            &expected,
+        ))
+    }
+}
+
+/// Validate `String` fields as v1 CUIDs, with `#[serde(with = "cuid1::serde::string")]`.
+pub mod string {
+    use super::*;
+
+    /// Serialize the string as-is.
+    pub fn serialize<S: Serializer>(value: &str, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(value)
+    }
+
+    /// Deserialize a string, failing if it is not a valid v1 CUID.
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+        deserialize_checked(deserializer, |value| is_cuid(value), "a v1 CUID")
+    }
+}
+
+/// Validate `String` fields as CUID slugs, with `#[serde(with = "cuid1::serde::slug")]`.
+pub mod slug {
+    use super::*;
+
+    /// Serialize the string as-is.
+    pub fn serialize<S: Serializer>(value: &str, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(value)
+    }
+
+    /// Deserialize a string, failing if it is not a valid CUID slug.
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+        deserialize_checked(deserializer, |value| is_slug(value), "a CUID slug")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ::serde::{Deserialize, Serialize};
+
+    use crate::{Cuid1, cuid, slug};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Record {
+        #[serde(with = "super::string")]
+        id: String,
+        #[serde(with = "super::slug")]
+        slug: String,
+    }
+
+    #[test]
+    fn round_trip() {
+        let id = Cuid1::new();
+        let json = serde_json::to_string(&id).unwrap();
+        assert_eq!(format!("\"{id}\""), json);
+        assert_eq!(id, serde_json::from_str(&json).unwrap());
+    }
+
+    #[test]
+    fn invalid() {
+        let err = serde_json::from_str::<Cuid1>("\"Not-A-Cuid\"").unwrap_err();
+        assert!(err.to_string().contains("expected a v1 CUID"), "{err}");
+        assert!(serde_json::from_str::<Cuid1>("12").is_err());
+    }
+
+    #[test]
+    fn helpers_round_trip() {
+        let record = Record {
+            id: cuid(),
+            slug: slug(),
+        };
+        let json = serde_json::to_string(&record).unwrap();
+        assert_eq!(record, serde_json::from_str(&json).unwrap());
+    }
+
+    #[test]
+    fn helpers_invalid() {
+        let long = cuid();
+        assert!(
+            serde_json::from_str::<Record>(&format!(r#"{{"id": "{long}", "slug": "{long}"}}"#))
+                .is_err()
+        );
+        assert!(serde_json::from_str::<Record>(r#"{"id": "1abc", "slug": "abc"}"#).is_err());
+        let multibyte = format!("é{}", &long[2..]);
+        assert!(
+            serde_json::from_str::<Record>(&format!(
+                r#"{{"id": "{multibyte}", "slug": "{}"}}"#,
+                slug()
+            ))
+            .is_err()
+        );
+    }
+}
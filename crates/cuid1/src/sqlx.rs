@@ -0,0 +1,47 @@
+//! sqlx support for v1 CUIDs, enabled with the `sqlx` feature.
+//!
+//! [`Cuid1`] implements `Type`, `Encode`, and `Decode` for every database
+//! which supports strings, including Postgres (`TEXT` and `VARCHAR`), SQLite,
+//! and MySQL. IDs are stored as text, and decoding fails for any value which
+//! is not a valid v1 CUID.
+
+use ::sqlx::{Database, Decode, Encode, Type, encode::IsNull, error::BoxDynError};
+
+use crate::Cuid1;
+
+impl<DB: Database> Type<DB> for Cuid1
+where
+    str: Type<DB>,
+{
+    fn type_info() -> DB::TypeInfo {
+        <str as Type<DB>>::type_info()
+    }
+
+    fn compatible(ty: &DB::TypeInfo) -> bool {
+        <str as Type<DB>>::compatible(ty)
+    }
+}
+impl<'q, DB: Database> Encode<'q, DB> for Cuid1
+where
+    for<'a> &'a str: Encode<'q, DB>,
+{
+    fn encode_by_ref(&self, buf: &mut DB::ArgumentBuffer) -> Result<IsNull, BoxDynError> {
+        <&str as Encode<DB>>::encode(self.as_str(), buf)
+    }
+
+    fn produces(&self) -> Option<DB::TypeInfo> {
+        <&str as Encode<DB>>::produces(&self.as_str())
+    }
+
+    fn size_hint(&self) -> usize {
+        <&str as Encode<DB>>::size_hint(&self.as_str())
+    }
+}
+impl<'r, DB: Database> Decode<'r, DB> for Cuid1
+where
+    &'r str: Decode<'r, DB>,
+{
+    fn decode(value: DB::ValueRef<'r>) -> Result<Self, BoxDynError> {
+        Ok(Self::try_from(<&str as Decode<DB>>::decode(value)?)?)
+    }
+}
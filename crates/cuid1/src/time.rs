@@ -1,27 +1,72 @@
-// std::time::SystemTime panics on WASM, so use a different library there.
-#[cfg(not(target_family = "wasm"))]
-use std::time::{SystemTime, UNIX_EPOCH};
-#[cfg(target_family = "wasm")]
-use web_time::{SystemTime, UNIX_EPOCH};
+use std::{
+    ops::RangeInclusive,
+    sync::{
+        PoisonError, RwLock,
+        atomic::{AtomicBool, Ordering},
+    },
+};
+
+use cuid_util::{Clock, ClockError, SystemClock};
 
 use crate::text::to_base_string;
 
+/// A clock set via [`crate::set_clock()`], replacing the system clock.
+static CLOCK: RwLock<Option<Box<dyn Clock>>> = RwLock::new(None);
+
+/// Whether `CLOCK` has been set, so that we don't need to take a lock to check
+/// it when using the system clock.
+static HAS_CLOCK: AtomicBool = AtomicBool::new(false);
+
+pub fn set_clock(clock: Option<Box<dyn Clock>>) {
+    let mut guard = CLOCK.write().unwrap_or_else(PoisonError::into_inner);
+    HAS_CLOCK.store(clock.is_some(), Ordering::Release);
+    *guard = clock;
+}
+
+/// Timestamps which encode to the 8 or 9 base 36 digits that a valid CUID
+/// may contain, from 1972 through the year 5188.
+const VALID_MILLIS: RangeInclusive<u128> = 36_u128.pow(7)..=36_u128.pow(9) - 1;
+
+/// Clamps a timestamp from the clock to one which produces a valid CUID, in
+/// case an injected clock reports a time far from the present.
+pub fn clamp_millis(millis: u128) -> u128 {
+    millis.clamp(*VALID_MILLIS.start(), *VALID_MILLIS.end())
+}
+
 pub fn timestamp() -> String {
-    SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        // millisecond timestamp to match javascript
-        .map(|time| time.as_millis())
-        .map(to_base_string)
-        .expect(
-            "Failed to calculate system timestamp! Current system time may be \
-                 set to before the Unix epoch, or time may otherwise be broken. \
-                 Cannot continue",
-        )
+    to_base_string(clamp_millis(timestamp_millis()))
+}
+
+pub fn try_timestamp() -> Result<String, ClockError> {
+    try_timestamp_millis().map(clamp_millis).map(to_base_string)
+}
+
+pub fn timestamp_millis() -> u128 {
+    if HAS_CLOCK.load(Ordering::Acquire)
+        && let Some(clock) = &*CLOCK.read().unwrap_or_else(PoisonError::into_inner)
+    {
+        return clock.now_millis();
+    }
+    SystemClock.now_millis()
+}
+
+pub fn try_timestamp_millis() -> Result<u128, ClockError> {
+    if HAS_CLOCK.load(Ordering::Acquire)
+        && let Some(clock) = &*CLOCK.read().unwrap_or_else(PoisonError::into_inner)
+    {
+        return clock.try_now_millis();
+    }
+    SystemClock.try_now_millis()
 }
 
 #[cfg(test)]
 mod time_tests {
-    use super::super::BASE;
+    // std::time::SystemTime panics on WASM, so use a different library there.
+    #[cfg(not(target_family = "wasm"))]
+    use std::time::{SystemTime, UNIX_EPOCH};
+    #[cfg(target_family = "wasm")]
+    use web_time::{SystemTime, UNIX_EPOCH};
+
     use super::*;
 
     // NOTE: this will start failing in ~2059, at which point this will need to
@@ -38,7 +83,7 @@ mod time_tests {
                 .duration_since(UNIX_EPOCH)
                 .unwrap()
                 .as_millis()
-                - u128::from_str_radix(&timestamp(), BASE as u32).unwrap())
+                - cuid_util::from_base_36(&timestamp()).unwrap())
                 < 5
         )
     }
@@ -0,0 +1,69 @@
+//! Tests for injected clocks.
+//!
+//! These live in their own test binary, since setting a clock affects every
+//! CUID generated in the process.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use cuid1::{Clock, ClockError, Cuid1};
+
+struct BrokenClock;
+impl Clock for BrokenClock {
+    fn now_millis(&self) -> u128 {
+        unreachable!()
+    }
+    fn try_now_millis(&self) -> Result<u128, ClockError> {
+        Err(ClockError)
+    }
+}
+
+#[test]
+fn injected_clock() {
+    static NOW: AtomicU64 = AtomicU64::new(1_535_421_552_101);
+    cuid1::set_clock(|| NOW.load(Ordering::Relaxed).into());
+
+    let id: Cuid1 = cuid1::cuid().parse().unwrap();
+    assert_eq!(1_535_421_552_101, id.timestamp_millis());
+    assert!(id.as_str().starts_with("cjld2cjxh"));
+
+    NOW.fetch_add(36, Ordering::Relaxed);
+    let id: Cuid1 = cuid1::cuid_inline().parse().unwrap();
+    assert_eq!(1_535_421_552_137, id.timestamp_millis());
+
+    // The last two characters of the timestamp go into the slug
+    assert!(cuid1::slug().starts_with("yh"));
+
+    // A clock close to the epoch is clamped to the earliest valid timestamp,
+    // but still fills the slug's timestamp
+    cuid1::set_clock(|| 5_u128);
+    let id = Cuid1::new();
+    assert_eq!(36_u64.pow(7), id.timestamp_millis());
+    assert_eq!(Ok(&id), id.as_str().parse().as_ref());
+    assert!(cuid1::is_cuid_strict(cuid1::cuid()));
+    assert!(cuid1::is_cuid_strict(cuid1::cuid_inline()));
+    let slug = cuid1::slug();
+    assert!(slug.starts_with("05"), "{slug}");
+    assert!(cuid1::is_slug(slug));
+
+    // A clock far in the future is clamped to the latest valid timestamp
+    cuid1::set_clock(|| u128::MAX);
+    assert!(cuid1::slug().starts_with("33"));
+    let id = Cuid1::new();
+    assert_eq!(36_u64.pow(9) - 1, id.timestamp_millis());
+    assert_eq!(Ok(&id), id.as_str().parse().as_ref());
+    let id = cuid1::cuid_inline();
+    assert!(id.starts_with("czzzzzzzzz"), "{id}");
+    assert!(cuid1::is_cuid_strict(id));
+
+    cuid1::set_clock(BrokenClock);
+    assert_eq!(Err(cuid1::Error::ClockBeforeEpoch), cuid1::try_cuid());
+    assert_eq!(
+        Err(cuid1::Error::ClockBeforeEpoch),
+        cuid1::try_cuid_inline()
+    );
+    assert_eq!(Err(cuid1::Error::ClockBeforeEpoch), cuid1::try_slug());
+
+    cuid1::reset_clock();
+    let id: Cuid1 = cuid1::cuid().parse().unwrap();
+    assert!(id.timestamp_millis() > 1_535_421_552_137);
+}
@@ -5,6 +5,13 @@ fn bench_create_id(c: &mut Criterion) {
     c.bench_function("generate cuid2", |b| b.iter(create_id));
 }
 
+fn bench_create_inline_id(c: &mut Criterion) {
+    let constructor = CuidConstructor::new();
+    c.bench_function("generate inline cuid2", |b| {
+        b.iter(|| constructor.create_inline())
+    });
+}
+
 fn bench_create_many_ids(c: &mut Criterion) {
     c.bench_function("generate many cuid2", |b| {
         b.iter(|| {
@@ -15,6 +22,29 @@ fn bench_create_many_ids(c: &mut Criterion) {
     });
 }
 
+/// Compares generating IDs one at a time in a loop with generating them in a
+/// batch, which shares setup between the IDs.
+fn bench_loop_vs_batch(c: &mut Criterion) {
+    let constructor = CuidConstructor::new();
+    let mut group = c.benchmark_group("10,000 cuid2");
+    group.bench_function("loop", |b| {
+        b.iter(|| {
+            (0..10_000)
+                .map(|_| constructor.create_id())
+                .collect::<Vec<_>>()
+        })
+    });
+    group.bench_function("batch", |b| b.iter(|| constructor.create_ids(10_000)));
+    let mut ids = Vec::with_capacity(10_000);
+    group.bench_function("extend", |b| {
+        b.iter(|| {
+            ids.clear();
+            constructor.extend_with_ids(&mut ids, 10_000);
+        })
+    });
+    group.finish();
+}
+
 fn bench_create_small_id(c: &mut Criterion) {
     let constructor = CuidConstructor::new().with_length(10);
     c.bench_function("generate small cuid2", |b| {
@@ -25,7 +55,9 @@ fn bench_create_small_id(c: &mut Criterion) {
 criterion_group!(
     cuid2,
     bench_create_id,
+    bench_create_inline_id,
     bench_create_many_ids,
+    bench_loop_vs_batch,
     bench_create_small_id
 );
 
@@ -0,0 +1,201 @@
+//! The characters used to encode CUIDs.
+//!
+//! See [`CuidConstructor::with_alphabet()`](crate::CuidConstructor::with_alphabet).
+
+use core::fmt;
+
+use rand::RngExt;
+
+use crate::Error;
+
+/// The largest supported alphabet: every ASCII letter and digit.
+const MAX_SIZE: usize = 62;
+
+/// The characters used to encode CUIDs.
+///
+/// The hash making up the body of a CUID is encoded using every character of
+/// the alphabet, while the first character of a CUID is one of its letters.
+/// By default, CUIDs use [`Alphabet::BASE36`], lowercase ASCII letters and
+/// digits.
+///
+/// Alphabets may contain between 2 and 62 distinct ASCII letters and digits,
+/// including at least one letter. Note that with fewer characters, each
+/// character of a CUID carries less entropy, so a smaller alphabet may call
+/// for a longer length.
+///
+/// ```
+/// use cuid2::{Alphabet, CuidConstructor};
+///
+/// let constructor = CuidConstructor::new().with_alphabet(Alphabet::BASE36_UPPER);
+/// let id = constructor.create_id();
+/// assert!(id.bytes().all(|b| b.is_ascii_uppercase() || b.is_ascii_digit()));
+/// assert!(constructor.is_valid(&id));
+///
+/// let hex = Alphabet::new("0123456789abcdef");
+/// assert!(Alphabet::try_new("0123456789").is_err());
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Alphabet {
+    digits: [u8; MAX_SIZE],
+    size: u8,
+    letters: [u8; MAX_SIZE],
+    letter_count: u8,
+    /// Whether each ASCII character is in the alphabet.
+    members: [bool; 128],
+}
+impl Alphabet {
+    /// Lowercase ASCII letters and digits, the default alphabet.
+    pub const BASE36: Self = Self::new("0123456789abcdefghijklmnopqrstuvwxyz");
+
+    /// Uppercase ASCII letters and digits, for case-insensitive systems
+    /// which prefer uppercase.
+    pub const BASE36_UPPER: Self = Self::new("0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ");
+
+    /// Lowercase ASCII letters and digits, without the easily confused `0`,
+    /// `1`, `i`, `l`, and `o`, for IDs which are read by people.
+    pub const UNAMBIGUOUS: Self = Self::new("23456789abcdefghjkmnpqrstuvwxyz");
+
+    /// Creates an alphabet from its characters, in ascending order of the
+    /// value they encode.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `digits` is not a valid alphabet. See
+    /// [`try_new()`](Self::try_new) for a non-panicking alternative.
+    pub const fn new(digits: &str) -> Self {
+        match Self::try_new(digits) {
+            Ok(alphabet) => alphabet,
+            Err(_) => panic!(
+                "CUID alphabet must be 2 to 62 distinct ASCII letters and digits, \
+                 including a letter"
+            ),
+        }
+    }
+
+    /// Creates an alphabet from its characters, in ascending order of the
+    /// value they encode, or returns an error if they are not a valid
+    /// alphabet.
+    pub const fn try_new(digits: &str) -> Result<Self, Error> {
+        let bytes = digits.as_bytes();
+        if bytes.len() < 2 || bytes.len() > MAX_SIZE {
+            return Err(Error::InvalidAlphabet);
+        }
+
+        let mut alphabet = Self {
+            digits: [0; MAX_SIZE],
+            // Cast safety: checked against MAX_SIZE above
+            size: bytes.len() as u8,
+            letters: [0; MAX_SIZE],
+            letter_count: 0,
+            members: [false; 128],
+        };
+        let mut idx = 0;
+        while idx < bytes.len() {
+            let byte = bytes[idx];
+            if !byte.is_ascii_alphanumeric() || alphabet.members[byte as usize] {
+                return Err(Error::InvalidAlphabet);
+            }
+            alphabet.members[byte as usize] = true;
+            alphabet.digits[idx] = byte;
+            if byte.is_ascii_alphabetic() {
+                alphabet.letters[alphabet.letter_count as usize] = byte;
+                alphabet.letter_count += 1;
+            }
+            idx += 1;
+        }
+
+        if alphabet.letter_count == 0 {
+            return Err(Error::InvalidAlphabet);
+        }
+        Ok(alphabet)
+    }
+
+    /// Returns the characters of the alphabet.
+    pub fn as_str(&self) -> &str {
+        core::str::from_utf8(&self.digits[..self.radix()]).expect("alphabets are ASCII")
+    }
+
+    /// Returns the number of characters in the alphabet.
+    pub const fn radix(&self) -> usize {
+        self.size as usize
+    }
+
+    /// Returns the character encoding `value`, which must be less than the
+    /// radix.
+    #[inline]
+    pub(crate) fn digit(&self, value: u8) -> u8 {
+        self.digits[usize::from(value)]
+    }
+
+    /// Selects a random letter from the alphabet.
+    #[inline]
+    pub(crate) fn random_letter(&self, rng: &mut (impl RngExt + ?Sized)) -> u8 {
+        self.letters[rng.random_range(0..usize::from(self.letter_count))]
+    }
+
+    /// Returns whether `character` is in the alphabet.
+    #[inline]
+    pub(crate) fn contains(&self, character: char) -> bool {
+        character.is_ascii() && self.members[character as usize]
+    }
+
+    /// Returns whether `character` is a letter in the alphabet.
+    #[inline]
+    pub(crate) fn contains_letter(&self, character: char) -> bool {
+        character.is_ascii_alphabetic() && self.contains(character)
+    }
+}
+impl Default for Alphabet {
+    fn default() -> Self {
+        Self::BASE36
+    }
+}
+impl fmt::Debug for Alphabet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Alphabet").field(&self.as_str()).finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn valid_alphabets() {
+        assert_eq!(36, Alphabet::BASE36.radix());
+        assert_eq!(36, Alphabet::BASE36_UPPER.radix());
+        assert_eq!(31, Alphabet::UNAMBIGUOUS.radix());
+        assert_eq!("ab", Alphabet::new("ab").as_str());
+        assert_eq!(
+            62,
+            Alphabet::new("0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ").radix()
+        );
+        assert_eq!(Alphabet::BASE36, Alphabet::default());
+        assert_eq!(
+            "Alphabet(\"23456789abcdefghjkmnpqrstuvwxyz\")",
+            format!("{:?}", Alphabet::UNAMBIGUOUS)
+        );
+    }
+
+    #[test]
+    fn invalid_alphabets() {
+        for digits in ["", "a", "0123", "aa", "ab-", "abé", &"a".repeat(63)] {
+            assert_eq!(
+                Err(Error::InvalidAlphabet),
+                Alphabet::try_new(digits),
+                "{digits}"
+            );
+        }
+    }
+
+    #[test]
+    fn membership() {
+        let alphabet = Alphabet::UNAMBIGUOUS;
+        assert!(alphabet.contains('2'));
+        assert!(alphabet.contains_letter('a'));
+        assert!(!alphabet.contains_letter('2'));
+        assert!(!alphabet.contains('l'));
+        assert!(!alphabet.contains('A'));
+        assert!(!alphabet.contains('é'));
+    }
+}
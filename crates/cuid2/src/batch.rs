@@ -0,0 +1,66 @@
+//! Generation of many CUIDs at once.
+//!
+//! See [`CuidConstructor::create_ids()`](crate::CuidConstructor::create_ids).
+
+use alloc::{borrow::Cow, string::String};
+
+use rand::RngExt;
+use sha3::Sha3_512;
+
+#[cfg(feature = "std")]
+use crate::FINGERPRINT;
+use crate::{CuidConstructor, Error, time_hasher};
+
+/// Shares the setup for generating CUIDs between many IDs.
+///
+/// The fingerprinter is called once, when the batch is created. The clock is
+/// read for every ID, but the hasher is only fed the timestamp when it
+/// changes, and is otherwise cloned from the last ID.
+pub(crate) struct Batch<'a> {
+    constructor: &'a CuidConstructor,
+    fingerprint: Option<Cow<'a, str>>,
+    /// The last timestamp read from the clock, and a hasher which has been
+    /// fed it. `None` until the clock is first read.
+    hasher: Option<(u128, Sha3_512)>,
+}
+impl<'a> Batch<'a> {
+    pub(crate) fn new(constructor: &'a CuidConstructor) -> Self {
+        Self {
+            constructor,
+            fingerprint: constructor.fingerprinter().map(|f| f.fingerprint()),
+            hasher: None,
+        }
+    }
+
+    /// Creates a batch which is only used on the current thread, so that the
+    /// thread's default fingerprint may also be fetched once, rather than
+    /// for every ID.
+    ///
+    /// IDs generated after a call to [`reseed()`](crate::reseed) still use the
+    /// fingerprint from before it.
+    #[cfg(feature = "std")]
+    pub(crate) fn on_current_thread(constructor: &'a CuidConstructor) -> Self {
+        let mut batch = Self::new(constructor);
+        // Seeded constructors derive their fingerprint from the seed instead
+        if batch.fingerprint.is_none() && constructor.seeded.is_none() {
+            batch.fingerprint = Some(Cow::Owned(FINGERPRINT.with_borrow(String::clone)));
+        }
+        batch
+    }
+
+    /// Creates the next CUID in the batch, using the provided random number
+    /// generator.
+    #[inline]
+    pub(crate) fn try_next(&mut self, rng: &mut (impl RngExt + ?Sized)) -> Result<String, Error> {
+        let time = self.constructor.current_time()?;
+        let hasher = match &self.hasher {
+            Some((last_time, hasher)) if *last_time == time => hasher,
+            _ => &self.hasher.insert((time, time_hasher(time))).1,
+        };
+
+        let (first_letter, body) =
+            self.constructor
+                .generate_at(rng, hasher.clone(), self.fingerprint.as_deref());
+        Ok(self.constructor.assemble(first_letter, body))
+    }
+}
@@ -0,0 +1,65 @@
+//! Diesel support for CUID2s, enabled with the `diesel` feature.
+//!
+//! [`Cuid2`](crate::Cuid2) may be used directly in Diesel models for `Text`
+//! columns, with any backend. IDs are stored as text, and loading fails for
+//! any value which is not a valid CUID2.
+//!
+//! A [`CuidConstructor`] may also be used as a `Text` expression, which
+//! generates a new ID each time the query is built. This is convenient for
+//! filling in an ID column on insert. Building the expression panics if an
+//! ID cannot be generated.
+
+use ::diesel::{expression::AsExpression, sql_types::Text};
+
+use crate::CuidConstructor;
+
+/// Generates a new ID each time the expression is built.
+///
+/// # Panics
+///
+/// Panics if an ID cannot be generated, like
+/// [`create_id()`](CuidConstructor::create_id). To handle the error instead,
+/// bind the result of [`try_create_id()`](CuidConstructor::try_create_id).
+impl AsExpression<Text> for &CuidConstructor {
+    type Expression = <String as AsExpression<Text>>::Expression;
+
+    fn as_expression(self) -> Self::Expression {
+        AsExpression::<Text>::as_expression(self.create_id())
+    }
+}
+
+#[cfg(all(test, not(target_family = "wasm")))]
+mod test {
+    use ::diesel::prelude::*;
+
+    use crate::{Cuid2, CuidConstructor};
+
+    ::diesel::table! {
+        users (id) {
+            id -> Text,
+        }
+    }
+
+    #[test]
+    fn default_on_insert() {
+        static CONSTRUCTOR: CuidConstructor = CuidConstructor::new().with_length(16);
+
+        let mut conn = SqliteConnection::establish(":memory:").unwrap();
+        ::diesel::sql_query("CREATE TABLE users (id TEXT PRIMARY KEY)")
+            .execute(&mut conn)
+            .unwrap();
+        ::diesel::insert_into(users::table)
+            .values(users::id.eq(&CONSTRUCTOR))
+            .execute(&mut conn)
+            .unwrap();
+        ::diesel::insert_into(users::table)
+            .values(users::id.eq(&CONSTRUCTOR))
+            .execute(&mut conn)
+            .unwrap();
+
+        let ids: Vec<Cuid2> = users::table.select(users::id).load(&mut conn).unwrap();
+        assert_eq!(2, ids.len());
+        assert_ne!(ids[0], ids[1]);
+        assert!(ids.iter().all(|id| id.as_str().len() == 16));
+    }
+}
@@ -0,0 +1,139 @@
+use core::fmt;
+
+use cuid_util::{ClockError, INLINE_CAPACITY};
+
+/// The error returned when a CUID cannot be generated.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Error {
+    /// The clock reported a time before the Unix epoch.
+    ClockBeforeEpoch,
+    /// The requested length is not a valid CUID length.
+    InvalidLength(u16),
+    /// The constructor has no clock, and there is no system clock to fall
+    /// back on. Only returned without the `std` feature.
+    MissingClock,
+    /// The requested prefix is empty or contains characters other than ASCII
+    /// letters and digits.
+    InvalidPrefix,
+    /// The requested prefix separator is not ASCII punctuation.
+    InvalidSeparator(char),
+    /// The requested alphabet is not between 2 and 62 distinct ASCII letters
+    /// and digits, or does not contain a letter.
+    InvalidAlphabet,
+    /// The constructor's IDs, including any prefix, are longer than an
+    /// [`InlineId`](crate::InlineId) can hold.
+    TooLongToInline(usize),
+}
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ClockBeforeEpoch => f.write_str("clock time is before the Unix epoch"),
+            Self::InvalidLength(length) => {
+                write!(f, "invalid CUID length {length}: must be at least 2")
+            }
+            Self::MissingClock => f.write_str("no clock was provided for CUID generation"),
+            Self::InvalidPrefix => {
+                f.write_str("invalid CUID prefix: must be non-empty ASCII letters and digits")
+            }
+            Self::InvalidSeparator(separator) => write!(
+                f,
+                "invalid CUID prefix separator {separator:?}: must be ASCII punctuation"
+            ),
+            Self::InvalidAlphabet => f.write_str(
+                "invalid CUID alphabet: must be 2 to 62 distinct ASCII letters and digits, \
+                 including a letter",
+            ),
+            Self::TooLongToInline(length) => write!(
+                f,
+                "CUID length {length} exceeds the inline capacity of {INLINE_CAPACITY}"
+            ),
+        }
+    }
+}
+impl core::error::Error for Error {}
+impl From<ClockError> for Error {
+    fn from(_: ClockError) -> Self {
+        Self::ClockBeforeEpoch
+    }
+}
+
+/// The reason a string is not a valid CUID2, as returned by
+/// [`validate_cuid2()`](crate::validate_cuid2),
+/// [`validate_slug()`](crate::validate_slug), and
+/// [`CuidConstructor::validate()`](crate::CuidConstructor::validate).
+///
+/// Lengths are measured in bytes, and positions in characters, starting from
+/// zero.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ValidationError {
+    /// The string is shorter than the minimum length.
+    TooShort { length: usize, min: usize },
+    /// The string is longer than the maximum length.
+    TooLong { length: usize, max: usize },
+    /// The first character is not a letter of the CUID's alphabet, by
+    /// default a lowercase ASCII letter.
+    InvalidStart(char),
+    /// A character is not in the CUID's alphabet, by default lowercase ASCII
+    /// letters and digits.
+    InvalidChar { position: usize, character: char },
+    /// The string does not start with the expected prefix and separator.
+    MissingPrefix,
+}
+impl ValidationError {
+    /// Adjusts lengths and positions for a string which was validated
+    /// after removing `offset` single-byte characters from its start.
+    pub(crate) fn offset(self, offset: usize) -> Self {
+        match self {
+            Self::TooShort { length, min } => Self::TooShort {
+                length: length + offset,
+                min: min + offset,
+            },
+            Self::TooLong { length, max } => Self::TooLong {
+                length: length + offset,
+                max: max + offset,
+            },
+            Self::InvalidChar {
+                position,
+                character,
+            } => Self::InvalidChar {
+                position: position + offset,
+                character,
+            },
+            Self::InvalidStart(_) | Self::MissingPrefix => self,
+        }
+    }
+}
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooShort { length, min } => {
+                write!(
+                    f,
+                    "CUID is too short: {length} bytes, expected at least {min}"
+                )
+            }
+            Self::TooLong { length, max } => {
+                write!(
+                    f,
+                    "CUID is too long: {length} bytes, expected at most {max}"
+                )
+            }
+            Self::InvalidStart(character) => write!(
+                f,
+                "CUID must start with a letter from its alphabet, found {character:?}"
+            ),
+            Self::InvalidChar {
+                position,
+                character,
+            } => write!(
+                f,
+                "invalid character {character:?} at position {position}, expected a \
+                 character from the CUID's alphabet"
+            ),
+            Self::MissingPrefix => f.write_str("CUID does not start with the expected prefix"),
+        }
+    }
+}
+impl core::error::Error for ValidationError {}
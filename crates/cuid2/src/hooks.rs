@@ -0,0 +1,84 @@
+//! Customization points for [`CuidConstructor`](crate::CuidConstructor).
+
+use alloc::{borrow::Cow, string::String, sync::Arc};
+#[cfg(target_has_atomic = "64")]
+use core::sync::atomic::{AtomicU64, Ordering};
+
+/// A source of counter values for CUID generation.
+///
+/// `CuidCounter` is implemented for closures returning a `u64`, for
+/// `AtomicU64`, and for `Arc`s of any other counter, so state can be shared
+/// between constructors:
+///
+/// ```
+/// use std::sync::{Arc, atomic::AtomicU64};
+///
+/// use cuid2::CuidConstructor;
+///
+/// let tenant_counter = Arc::new(AtomicU64::new(0));
+/// let constructor = CuidConstructor::new().with_counter(Arc::clone(&tenant_counter));
+/// constructor.create_id();
+///
+/// assert_eq!(1, tenant_counter.load(std::sync::atomic::Ordering::Relaxed));
+/// ```
+pub trait CuidCounter: Send + Sync {
+    /// Returns the counter value for the next ID, advancing the counter.
+    fn next_count(&self) -> u64;
+}
+impl<F> CuidCounter for F
+where
+    F: Fn() -> u64 + Send + Sync,
+{
+    fn next_count(&self) -> u64 {
+        self()
+    }
+}
+#[cfg(target_has_atomic = "64")]
+impl CuidCounter for AtomicU64 {
+    fn next_count(&self) -> u64 {
+        // fetch_add() wraps on overflow
+        self.fetch_add(1, Ordering::Relaxed)
+    }
+}
+impl<T: CuidCounter + ?Sized> CuidCounter for Arc<T> {
+    fn next_count(&self) -> u64 {
+        (**self).next_count()
+    }
+}
+
+/// A source of fingerprints for CUID generation.
+///
+/// `Fingerprinter` is implemented for closures returning a `String`, and for
+/// `String` and `&'static str`, which act as fixed fingerprints, e.g. one
+/// loaded from configuration at startup:
+///
+/// ```
+/// use cuid2::CuidConstructor;
+///
+/// let fingerprint = format!("{}-{}", "my-host", std::process::id());
+/// let constructor = CuidConstructor::new().with_fingerprinter(fingerprint);
+///
+/// assert!(cuid2::is_cuid2(constructor.create_id()));
+/// ```
+pub trait Fingerprinter: Send + Sync {
+    /// Returns the fingerprint for the next ID.
+    fn fingerprint(&self) -> Cow<'_, str>;
+}
+impl<F> Fingerprinter for F
+where
+    F: Fn() -> String + Send + Sync,
+{
+    fn fingerprint(&self) -> Cow<'_, str> {
+        Cow::Owned(self())
+    }
+}
+impl Fingerprinter for String {
+    fn fingerprint(&self) -> Cow<'_, str> {
+        Cow::Borrowed(self)
+    }
+}
+impl Fingerprinter for &'static str {
+    fn fingerprint(&self) -> Cow<'_, str> {
+        Cow::Borrowed(self)
+    }
+}
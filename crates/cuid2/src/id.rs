@@ -0,0 +1,258 @@
+//! A strongly-typed CUID2 value.
+
+use alloc::{borrow::ToOwned, string::String};
+use core::{borrow::Borrow, fmt, str::FromStr};
+
+use cuid_util::{from_base_36, to_base_36};
+
+#[cfg(feature = "std")]
+use crate::DEFAULT_CONSTRUCTOR;
+use crate::{ValidationError, is_cuid2, validate_cuid2};
+
+/// A CUID2, guaranteed to have been either generated by this crate or
+/// checked with [`is_cuid2`].
+///
+/// Using this type rather than a bare `String` prevents arbitrary strings
+/// from being passed where an ID is expected.
+///
+/// ```
+/// use cuid2::Cuid2;
+///
+/// let id = Cuid2::new();
+/// assert!(cuid2::is_cuid2(&id));
+///
+/// let parsed: Cuid2 = id.as_str().parse().unwrap();
+/// assert_eq!(id, parsed);
+///
+/// assert!("not a cuid!".parse::<Cuid2>().is_err());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Cuid2(String);
+impl Cuid2 {
+    /// The length of the longest ID which can be converted to a `u128` or to
+    /// bytes. Since `36^24 < 2^128`, IDs of the default length always fit.
+    pub const MAX_BINARY_LENGTH: usize = 24;
+
+    /// Creates a new CUID2 with the default settings.
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn new() -> Self {
+        DEFAULT_CONSTRUCTOR.create_id_typed()
+    }
+
+    /// Wraps a string which is known to be a valid CUID2, either because it
+    /// was just generated or because it has already been validated.
+    #[inline]
+    pub(crate) fn from_string_unchecked(id: String) -> Self {
+        debug_assert!(is_cuid2(&id), "generated invalid CUID2 {id}");
+        Self(id)
+    }
+
+    /// Returns the ID as a string slice.
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Consumes the ID, returning the inner `String`.
+    #[inline]
+    pub fn into_string(self) -> String {
+        self.0
+    }
+
+    /// Packs the ID into a `u128`, reading it as a base 36 number, or returns
+    /// `None` if it is longer than [`MAX_BINARY_LENGTH`](Self::MAX_BINARY_LENGTH).
+    ///
+    /// Since CUID2s start with a letter, they never have leading zeros, so
+    /// the conversion is lossless, and [`from_u128()`](Self::from_u128)
+    /// restores the original ID.
+    ///
+    /// ```
+    /// use cuid2::Cuid2;
+    ///
+    /// let id = Cuid2::new();
+    /// let packed = id.to_u128().unwrap();
+    /// assert_eq!(id, Cuid2::from_u128(packed).unwrap());
+    /// ```
+    pub fn to_u128(&self) -> Option<u128> {
+        if self.0.len() > Self::MAX_BINARY_LENGTH {
+            return None;
+        }
+        // Panic safety: the ID only contains base 36 digits, and no more
+        // than 24 of them, which is always representable as a u128.
+        Some(from_base_36(&self.0).expect("CUID2 digits are validated on construction"))
+    }
+
+    /// Packs the ID into 16 big-endian bytes, suitable for a `BYTEA`, `BLOB`,
+    /// or `UUID` column, or returns `None` if it is longer than
+    /// [`MAX_BINARY_LENGTH`](Self::MAX_BINARY_LENGTH). See
+    /// [`to_u128()`](Self::to_u128).
+    pub fn to_bytes(&self) -> Option<[u8; 16]> {
+        self.to_u128().map(u128::to_be_bytes)
+    }
+
+    /// Unpacks an ID produced by [`to_u128()`](Self::to_u128), or returns an
+    /// error if the number is not a CUID2 of at most
+    /// [`MAX_BINARY_LENGTH`](Self::MAX_BINARY_LENGTH) characters.
+    pub fn from_u128(number: u128) -> Result<Self, ValidationError> {
+        let id = to_base_36(number);
+        if id.len() > Self::MAX_BINARY_LENGTH {
+            return Err(ValidationError::TooLong {
+                length: id.len(),
+                max: Self::MAX_BINARY_LENGTH,
+            });
+        }
+        Self::try_from(id)
+    }
+
+    /// Unpacks an ID produced by [`to_bytes()`](Self::to_bytes). See
+    /// [`from_u128()`](Self::from_u128).
+    pub fn from_bytes(bytes: [u8; 16]) -> Result<Self, ValidationError> {
+        Self::from_u128(u128::from_be_bytes(bytes))
+    }
+}
+#[cfg(feature = "std")]
+impl Default for Cuid2 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl fmt::Display for Cuid2 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+impl AsRef<str> for Cuid2 {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+impl Borrow<str> for Cuid2 {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+impl From<Cuid2> for String {
+    fn from(id: Cuid2) -> Self {
+        id.0
+    }
+}
+impl FromStr for Cuid2 {
+    type Err = ValidationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from(s)
+    }
+}
+impl TryFrom<&str> for Cuid2 {
+    type Error = ValidationError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        validate_cuid2(value)?;
+        Ok(Self::from_string_unchecked(value.to_owned()))
+    }
+}
+impl TryFrom<String> for Cuid2 {
+    type Error = ValidationError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        validate_cuid2(&value)?;
+        Ok(Self::from_string_unchecked(value))
+    }
+}
+
+crate::__define_cuid_sqlx!(Cuid2);
+crate::__define_cuid_diesel!(Cuid2);
+crate::__define_cuid_rusqlite!(Cuid2);
+
+#[cfg(test)]
+mod test {
+    #[cfg(feature = "std")]
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn generated_ids_are_valid() {
+        let id = Cuid2::new();
+        assert!(is_cuid2(&id));
+        assert_eq!(24, id.as_str().len());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn parse_round_trip() {
+        let id = Cuid2::new();
+        assert_eq!(id, id.to_string().parse().unwrap());
+        assert_eq!(id, Cuid2::try_from(id.as_str()).unwrap());
+        assert_eq!(id, Cuid2::try_from(id.clone().into_string()).unwrap());
+    }
+
+    #[test]
+    fn parse_invalid() {
+        assert_eq!(
+            Err(ValidationError::TooShort { length: 0, min: 2 }),
+            "".parse::<Cuid2>()
+        );
+        assert_eq!(
+            Err(ValidationError::InvalidStart('1')),
+            "1abc".parse::<Cuid2>()
+        );
+        assert_eq!(
+            Err(ValidationError::InvalidChar {
+                position: 1,
+                character: 'B'
+            }),
+            Cuid2::try_from("aBc".to_string())
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn binary_round_trip() {
+        let id = Cuid2::new();
+        let packed = id.to_u128().unwrap();
+        assert_eq!(id, Cuid2::from_u128(packed).unwrap());
+        assert_eq!(packed.to_be_bytes(), id.to_bytes().unwrap());
+        assert_eq!(id, Cuid2::from_bytes(id.to_bytes().unwrap()).unwrap());
+
+        // Short IDs are not padded, so they are restored at the same length
+        let short = Cuid2::try_from("a0").unwrap();
+        assert_eq!(Some(360), short.to_u128());
+        assert_eq!(short, Cuid2::from_u128(360).unwrap());
+
+        let longest = Cuid2::try_from("z".repeat(24)).unwrap();
+        assert_eq!(Some(36_u128.pow(24) - 1), longest.to_u128());
+        assert_eq!(longest, Cuid2::from_u128(36_u128.pow(24) - 1).unwrap());
+    }
+
+    #[test]
+    fn binary_invalid() {
+        assert_eq!(None, Cuid2::try_from("a".repeat(25)).unwrap().to_u128());
+        assert_eq!(None, Cuid2::try_from("a".repeat(25)).unwrap().to_bytes());
+        assert_eq!(
+            Err(ValidationError::TooShort { length: 1, min: 2 }),
+            Cuid2::from_u128(10)
+        );
+        assert_eq!(
+            Err(ValidationError::InvalidStart('9')),
+            Cuid2::from_u128(9 * 36 + 10)
+        );
+        assert_eq!(
+            Err(ValidationError::TooLong {
+                length: 25,
+                max: 24
+            }),
+            Cuid2::from_bytes([255; 16])
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn lookup_by_str() {
+        let id = Cuid2::new();
+        let set = HashSet::from([id.clone()]);
+        assert!(set.contains(id.as_str()));
+    }
+}
@@ -0,0 +1,174 @@
+//! Endless sources of CUIDs.
+//!
+//! See [`CuidConstructor::iter()`](crate::CuidConstructor::iter).
+
+use alloc::string::String;
+use core::iter::FusedIterator;
+#[cfg(feature = "futures")]
+use core::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+#[cfg(feature = "futures")]
+use futures_core::{Stream, stream::FusedStream};
+use rand::rngs::ThreadRng;
+
+use crate::{CuidConstructor, Error, batch::Batch};
+
+/// An iterator which yields new CUIDs forever.
+///
+/// Created by [`CuidConstructor::iter()`]. The constructor's fingerprinter,
+/// or the thread's default fingerprint, is fetched once, when the iterator
+/// is created, and the random number generator is retrieved once and
+/// reused for every ID. The timestamp is only hashed again when the clock
+/// reports a new millisecond, so the only allocation for each item is the
+/// returned `String`.
+///
+/// # Panics
+///
+/// Iteration panics if the constructor's clock reports a time before the
+/// Unix epoch.
+pub struct Cuids<'a> {
+    inner: TryCuids<'a>,
+}
+impl<'a> Cuids<'a> {
+    pub(crate) fn new(constructor: &'a CuidConstructor) -> Self {
+        Self {
+            inner: TryCuids::new(constructor),
+        }
+    }
+}
+impl Iterator for Cuids<'_> {
+    type Item = String;
+
+    #[inline]
+    fn next(&mut self) -> Option<String> {
+        self.inner
+            .next()
+            .map(|id| id.unwrap_or_else(|err| panic!("Failed to create CUID: {err}")))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+impl FusedIterator for Cuids<'_> {}
+
+/// An iterator which yields new CUIDs forever, or an error for each ID which
+/// cannot be generated.
+///
+/// Created by [`CuidConstructor::try_iter()`]. It is otherwise identical to
+/// [`Cuids`]. An error does not end the iteration, so the next ID is
+/// generated normally if the clock recovers.
+pub struct TryCuids<'a> {
+    batch: Batch<'a>,
+    rng: ThreadRng,
+}
+impl<'a> TryCuids<'a> {
+    pub(crate) fn new(constructor: &'a CuidConstructor) -> Self {
+        Self {
+            batch: Batch::on_current_thread(constructor),
+            rng: rand::rng(),
+        }
+    }
+}
+impl Iterator for TryCuids<'_> {
+    type Item = Result<String, Error>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Result<String, Error>> {
+        Some(self.batch.try_next(&mut self.rng))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
+}
+impl FusedIterator for TryCuids<'_> {}
+
+/// A stream which yields new CUIDs forever, without ever waiting.
+///
+/// Created by [`CuidConstructor::stream()`]. Unlike [`Cuids`], the stream is
+/// `Send`, so it may be held across `.await` points in multithreaded
+/// runtimes. It retrieves the thread-local random number generator and
+/// default fingerprint for each ID, since the task polling it may move
+/// between threads.
+///
+/// # Panics
+///
+/// Polling panics if the constructor's clock reports a time before the Unix
+/// epoch.
+#[cfg(feature = "futures")]
+pub struct CuidStream<'a> {
+    inner: TryCuidStream<'a>,
+}
+#[cfg(feature = "futures")]
+impl<'a> CuidStream<'a> {
+    pub(crate) fn new(constructor: &'a CuidConstructor) -> Self {
+        Self {
+            inner: TryCuidStream::new(constructor),
+        }
+    }
+}
+#[cfg(feature = "futures")]
+impl Stream for CuidStream<'_> {
+    type Item = String;
+
+    #[inline]
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<String>> {
+        Pin::new(&mut self.inner)
+            .poll_next(cx)
+            .map(|id| id.map(|id| id.unwrap_or_else(|err| panic!("Failed to create CUID: {err}"))))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+#[cfg(feature = "futures")]
+impl FusedStream for CuidStream<'_> {
+    fn is_terminated(&self) -> bool {
+        false
+    }
+}
+
+/// A stream which yields new CUIDs forever, or an error for each ID which
+/// cannot be generated.
+///
+/// Created by [`CuidConstructor::try_stream()`]. It is otherwise identical to
+/// [`CuidStream`], and like [`TryCuids`], an error does not end the stream.
+#[cfg(feature = "futures")]
+pub struct TryCuidStream<'a> {
+    batch: Batch<'a>,
+}
+#[cfg(feature = "futures")]
+impl<'a> TryCuidStream<'a> {
+    pub(crate) fn new(constructor: &'a CuidConstructor) -> Self {
+        Self {
+            batch: Batch::new(constructor),
+        }
+    }
+}
+#[cfg(feature = "futures")]
+impl Stream for TryCuidStream<'_> {
+    type Item = Result<String, Error>;
+
+    #[inline]
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<String, Error>>> {
+        Poll::Ready(Some(self.batch.try_next(&mut rand::rng())))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
+}
+#[cfg(feature = "futures")]
+impl FusedStream for TryCuidStream<'_> {
+    fn is_terminated(&self) -> bool {
+        false
+    }
+}
@@ -53,22 +53,142 @@
 //! > cuid2
 //! y3cfw1hafbtezzflns334sb2
 //! ```
+//!
+//! ## Typed IDs
+//!
+//! The [`Cuid2`] type may be used instead of a bare `String` to ensure that
+//! only generated or validated IDs are passed around:
+//!
+//! ```
+//! use cuid2::{Cuid2, CuidConstructor};
+//!
+//! let id: Cuid2 = cuid2::create_id_typed();
+//! let other: Cuid2 = CuidConstructor::new().with_length(16).create_id_typed();
+//! let parsed: Cuid2 = "y3cfw1hafbtezzflns334sb2".parse().unwrap();
+//!
+//! assert!("not_a_cuid".parse::<Cuid2>().is_err());
+//! ```
+//!
+//! For self-describing IDs like `usr_y3cfw1hafbtezzflns334sb2`, use
+//! [`CuidConstructor::with_prefix()`], or [`define_cuid!`] to define a
+//! distinct type for each prefix:
+//!
+//! ```
+//! cuid2::define_cuid!(pub UserId, "usr");
+//!
+//! let id = UserId::new();
+//! assert!(id.as_str().starts_with("usr_"));
+//! ```
+//!
+//! ## Features
+//!
+//! - `std` (enabled by default): uses the standard library for thread-local
+//!   counters and fingerprints, the system clock, and the thread-local RNG.
+//! - `serde`: implements `Serialize` and `Deserialize` for [`Cuid2`], and
+//!   provides helpers for validating `String` fields. See the `serde` module.
+//! - `futures`: provides [`CuidConstructor::stream()`], an endless `Stream`
+//!   of CUIDs. Implies `std`.
+//! - `sqlx`: implements sqlx's `Type`, `Encode`, and `Decode` for [`Cuid2`]
+//!   and for types from [`define_cuid!`], storing them as text in any
+//!   database which supports strings. Decoding fails for invalid IDs.
+//!   Implies `std`.
+//! - `diesel`: lets [`Cuid2`] and types from [`define_cuid!`] be used in
+//!   Diesel models for `Text` columns, failing to load invalid IDs. A
+//!   [`CuidConstructor`] may be used as an expression to generate IDs on
+//!   insert. Implies `std`.
+//! - `rusqlite`: implements rusqlite's `ToSql` and `FromSql` for [`Cuid2`]
+//!   and for types from [`define_cuid!`], failing to read invalid IDs, and
+//!   provides SQLite functions for generating IDs. See the `rusqlite`
+//!   module. Implies `std`.
+//!
+//! ## `no_std`
+//!
+//! Without the `std` feature, this crate is `no_std`, but still requires
+//! `alloc`. The caller must then provide a [`Clock`] for each constructor,
+//! and an RNG for each ID, via
+//! [`CuidConstructor::create_id_with_rng()`]. Unless a counter or
+//! fingerprinter is provided, each ID's counter and fingerprint are drawn
+//! from the RNG.
+//!
+//! ```
+//! use cuid2::CuidConstructor;
+//! use rand::SeedableRng;
+//!
+//! // e.g. an RNG seeded from a hardware entropy source
+//! let mut rng = rand::rngs::ChaCha8Rng::seed_from_u64(0x5eed);
+//! let constructor = CuidConstructor::new().with_clock(|| 1_700_000_000_000);
+//!
+//! let id = constructor.create_id_with_rng(&mut rng);
+//! assert!(cuid2::is_cuid2(id));
+//! ```
 
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+mod alphabet;
+#[cfg(feature = "std")]
+mod batch;
+#[cfg(feature = "diesel")]
+mod diesel;
+mod error;
+mod hooks;
+mod id;
+#[cfg(feature = "std")]
+mod iter;
+mod prefixed;
+#[cfg(feature = "rusqlite")]
+pub mod rusqlite;
+mod seeded;
+#[cfg(feature = "serde")]
+pub mod serde;
+
+#[cfg(feature = "std")]
+use alloc::vec::Vec;
+use alloc::{boxed::Box, string::String};
+#[cfg(feature = "std")]
 use std::{
-    cell::RefCell,
+    cell::{Cell, RefCell},
     hash::{Hash, Hasher},
 };
 
-// std::time::SystemTime panics on WASM, so use a different library there.
-#[cfg(not(target_family = "wasm"))]
-use std::time::{SystemTime, UNIX_EPOCH};
-#[cfg(target_family = "wasm")]
-use web_time::{SystemTime, UNIX_EPOCH};
-
-use num::bigint;
-use rand::RngExt;
+#[cfg(feature = "std")]
+use cuid_util::INLINE_CAPACITY;
+use cuid_util::{MAX_DIGITS_512, radix_digits_512};
+use rand::{Rng, RngExt};
 use sha3::{Digest, Sha3_512};
 
+pub use alphabet::Alphabet;
+#[cfg(feature = "std")]
+pub use cuid_util::SystemClock;
+pub use cuid_util::{Clock, ClockError, InlineId};
+pub use error::{Error, ValidationError};
+pub use hooks::{CuidCounter, Fingerprinter};
+pub use id::Cuid2;
+#[cfg(feature = "futures")]
+pub use iter::{CuidStream, TryCuidStream};
+#[cfg(feature = "std")]
+pub use iter::{Cuids, TryCuids};
+
+/// Items used by macros, which are expanded in other crates. Not public API.
+#[doc(hidden)]
+pub mod __private {
+    pub use alloc::string::String;
+
+    #[cfg(feature = "diesel")]
+    pub use ::diesel;
+    #[cfg(feature = "rusqlite")]
+    pub use ::rusqlite;
+    #[cfg(feature = "sqlx")]
+    pub use ::sqlx;
+    #[cfg(feature = "serde")]
+    pub use serde;
+}
+
+#[cfg(feature = "std")]
+use batch::Batch;
+use seeded::{SEEDED_TIMESTAMP, Seeded};
+
 // =============================================================================
 // CONSTANTS
 // =============================================================================
@@ -77,8 +197,12 @@ use sha3::{Digest, Sha3_512};
 #[doc(hidden)]
 pub const DEFAULT_LENGTH: u8 = 24;
 const BIG_LENGTH: u8 = 32;
-// valid characters to start an ID
-const STARTING_CHARS: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
+// upper bound for the randomly initialized counter value
+const MAX_COUNTER_INIT: u64 = 476_782_367;
+// the shortest possible ID: a starting character and one character of hash
+const MIN_LENGTH: u16 = 2;
+// digits of a base 36 number, in order
+const BASE_36_DIGITS: &[u8; 36] = b"0123456789abcdefghijklmnopqrstuvwxyz";
 
 // =============================================================================
 // THREAD LOCALS
@@ -87,7 +211,11 @@ const STARTING_CHARS: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
 // - 64-bit counter, randomly initialized to some value between 0 and 2056, inclusive
 // - fingerprint, a hash with added entropy, derived from a random number between
 //   2063 and 4125, inclusive, the process ID, and the thread ID
+//
+// These require the standard library. Without it, counters and fingerprints
+// are drawn from the caller's RNG instead.
 
+#[cfg(feature = "std")]
 fn fingerprint() -> String {
     let mut rng = rand::rng();
     hash(
@@ -105,18 +233,19 @@ fn fingerprint() -> String {
     )
 }
 
+#[cfg(feature = "std")]
 thread_local! {
     /// Value used to initialize the counter. After the counter hits u64::MAX, it
     /// will roll back to this value.
     // Updated 2023-08-08 to match updated reference implementation, which notes:
     // > ~22k hosts before 50% chance of initial counter collision
     // > with a remaining counter range of 9.0e+15 in JavaScript.
-    static COUNTER_INIT: u64 = rand::random_range(0..476_782_367);
+    static COUNTER_INIT: Cell<u64> = Cell::new(random_counter_init());
 
     /// Use an individual counter per thread, starting at a randomly initialized value.
     ///
     /// Range of randomly initialized values taken from reference implementation.
-    static COUNTER: RefCell<u64> = COUNTER_INIT.with(|val| RefCell::new(*val));
+    static COUNTER: RefCell<u64> = RefCell::new(COUNTER_INIT.get());
 
     /// Fingerprint! The original implementation is a hash of:
     /// - stringified keys of the global object
@@ -129,7 +258,15 @@ thread_local! {
     ///
     /// This is pretty non-language, non-system dependent, so it allows us to
     /// compile to wasm and so on.
-    static FINGERPRINT: String = fingerprint();
+    ///
+    /// Regenerated by [`reseed()`], since a forked child process would
+    /// otherwise share its parent's fingerprint.
+    static FINGERPRINT: RefCell<String> = RefCell::new(fingerprint());
+}
+
+#[cfg(feature = "std")]
+fn random_counter_init() -> u64 {
+    rand::random_range(0..MAX_COUNTER_INIT)
 }
 
 // Hashing
@@ -148,7 +285,9 @@ thread_local! {
 // ```
 //
 // We don't drop the first character, because it doesn't actually affect the
-// histogram (the comment in the reference implementation is incorrect).
+// histogram (the comment in the reference implementation is incorrect). We
+// keep the low-order digits of the digest rather than the leading ones, since
+// only they can be produced without converting the whole digest.
 fn hash<S: AsRef<[u8]>, T: IntoIterator<Item = S>>(input: T, length: u16) -> String {
     let mut hasher = Sha3_512::new();
 
@@ -156,24 +295,42 @@ fn hash<S: AsRef<[u8]>, T: IntoIterator<Item = S>>(input: T, length: u16) -> Str
         hasher.update(block.as_ref());
     }
 
-    // 512 bits (64 bytes) of data ([u8; 64])
-    let hash = hasher.finalize();
-
-    // We'll convert the bytes directly to a big, unsigned int and then use
-    // its builtin radix conversion.
-    //
-    // We don't use bigint for the rest of our base conversions, because it's
-    // significantly slower, but we use it here since we need to deal with the
-    // 512-bit integer from the hash function.
-    let mut res = bigint::BigUint::from_bytes_be(&hash).to_str_radix(36);
-
-    // Note that truncate panics if the length does not fall on a char boundary,
-    // but we don't need to worry about that since all the chars will be ASCII.
-    res.truncate(length.into());
-
+    let mut res = String::with_capacity(length.into());
+    encode_digest(hasher, length.into(), &Alphabet::BASE36, |ch| res.push(ch));
     res
 }
 
+/// Finalize the hasher, passing exactly `length` digits of the alphabet
+/// derived from its digest to `push`, so that they may be written directly
+/// to their destination.
+///
+/// A 512-bit digest only provides around 99 base 36 digits. If more are
+/// needed, the digest is chained: the digest is hashed again, and the digits
+/// of each successive digest are appended until there are enough. Outputs
+/// which fit in the first digest are unaffected by the chaining.
+fn encode_digest(hasher: Sha3_512, length: usize, alphabet: &Alphabet, mut push: impl FnMut(char)) {
+    // 512 bits (64 bytes) of data ([u8; 64])
+    let mut hash = hasher.finalize();
+
+    // We treat the bytes as a big-endian 512-bit integer, and convert it to
+    // the alphabet's radix on the stack, producing only the low-order digits
+    // we need.
+    let mut digits = [0; MAX_DIGITS_512];
+    let mut remaining = length;
+    // Cast safety: alphabets have at most 62 characters
+    let radix = alphabet.radix() as u8;
+    loop {
+        for &digit in radix_digits_512(&hash.into(), radix, remaining, &mut digits) {
+            push(char::from(alphabet.digit(digit)));
+            remaining -= 1;
+        }
+        if remaining == 0 {
+            return;
+        }
+        hash = Sha3_512::digest(hash);
+    }
+}
+
 // Other Utility Functions
 // =======================
 
@@ -197,21 +354,60 @@ fn hash<S: AsRef<[u8]>, T: IntoIterator<Item = S>>(input: T, length: u16) -> Str
 /// ```
 #[inline]
 pub fn is_cuid2<S: AsRef<str>>(to_check: S) -> bool {
+    validate_cuid2(to_check).is_ok()
+}
+
+/// Check whether a string is a legitimate CUID2, returning the reason if not.
+///
+/// ```rust
+/// use cuid2::ValidationError;
+///
+/// assert_eq!(Ok(()), cuid2::validate_cuid2(cuid2::create_id()));
+/// assert_eq!(
+///     Err(ValidationError::InvalidChar { position: 3, character: '_' }),
+///     cuid2::validate_cuid2("aaa_1aaa")
+/// );
+/// ```
+#[inline]
+pub fn validate_cuid2<S: AsRef<str>>(to_check: S) -> Result<(), ValidationError> {
     const MAX_LENGTH: usize = BIG_LENGTH as usize;
-    is_cuid2_inner::<S, MAX_LENGTH>(to_check)
+    validate_cuid2_inner(
+        to_check.as_ref(),
+        MIN_LENGTH.into(),
+        MAX_LENGTH,
+        &Alphabet::BASE36,
+    )
 }
 
-fn is_cuid2_inner<S: AsRef<str>, const MAX_LENGTH: usize>(to_check: S) -> bool {
-    let to_check = to_check.as_ref().as_bytes();
+/// Validates a CUID2 with a length between `min` and `max` bytes, inclusive,
+/// encoded with the given alphabet.
+fn validate_cuid2_inner(
+    to_check: &str,
+    min: usize,
+    max: usize,
+    alphabet: &Alphabet,
+) -> Result<(), ValidationError> {
+    let length = to_check.len();
+    if length < min {
+        return Err(ValidationError::TooShort { length, min });
+    }
+    if length > max {
+        return Err(ValidationError::TooLong { length, max });
+    }
 
-    if (2..=MAX_LENGTH).contains(&to_check.len())
-        && let [first, tail @ ..] = to_check
+    let mut chars = to_check.chars();
+    if let Some(first) = chars.next()
+        && !alphabet.contains_letter(first)
     {
-        return STARTING_CHARS.contains(first)
-            && tail.iter().all(|x| matches!(x, b'0'..=b'9' | b'a'..=b'z'));
+        return Err(ValidationError::InvalidStart(first));
+    }
+    match chars.enumerate().find(|(_, c)| !alphabet.contains(*c)) {
+        Some((idx, character)) => Err(ValidationError::InvalidChar {
+            position: idx + 1,
+            character,
+        }),
+        None => Ok(()),
     }
-
-    false
 }
 
 /// Return whether a string is a legitimate CUID.
@@ -222,69 +418,95 @@ pub fn is_cuid<S: AsRef<str>>(to_check: S) -> bool {
     is_cuid2(to_check)
 }
 
-/// Creates a random string of the specified length.
-fn create_entropy(length: u16, rng: &mut impl RngExt) -> String {
-    let length: usize = length.into();
-
-    // String will not exceed this length because we push one char
-    // to it in each loop iteration.
-    let mut result = String::with_capacity(length);
-    while result.len() < length {
-        // Ultimately, matches reference implementation logic as of 2023-08-08,
-        // which is:
-        // ```js
-        // entropy = entropy + Math.floor(random() * 36).toString(36);
-        // ```
-
-        let range_bottom = 0;
-        let range_top = 36;
-        debug_assert_eq!(0, range_bottom);
-        debug_assert_eq!(36, range_top);
-
-        let random_val = rng.random_range(range_bottom..range_top);
-        debug_assert!(random_val < 36);
-
-        result.push(char::from_digit(random_val, 36).expect("range is within radix"));
-    }
-
-    result
-}
-
-/// Retrieves the current timestmap and converts to Base36.
-fn get_timestamp() -> u128 {
-    SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        // Use timestamp as milliseconds to match JS implementation
-        .map(|time| time.as_millis())
-        // Panic safety: `.duration_since()` fails if the end time is not
-        // later than the start time, so this will only fail if the system
-        // time is before 1970-01-01. It is impossible on Unix systems to set
-        // a time before then, since the entire system uses a 32 or 64 bit
-        // unsigned integer for time, where zero is midnight 1970-01-01.
-        //
-        // If you are on a system that for some reason both can be and needs to
-        // be set >50 years in the past AND this library not working is a
-        // problem for you, please feel free to reach out.
-        .expect(
-            "Failed to calculate system timestamp! Current system time may be \
-                 set to before the Unix epoch, or time may otherwise be broken. \
-                 Cannot continue",
-        )
+/// Feeds a random base 36 string of the specified length into the hasher.
+///
+/// The string is generated in fixed-size chunks on the stack. Hashing it in
+/// chunks yields the same digest as hashing it all at once, but avoids
+/// allocating a buffer for the whole string.
+fn hash_entropy(hasher: &mut Sha3_512, length: u16, rng: &mut (impl RngExt + ?Sized)) {
+    const CHUNK_SIZE: usize = 32;
+
+    let mut chunk = [0_u8; CHUNK_SIZE];
+    let mut remaining = usize::from(length);
+    while remaining > 0 {
+        let chunk_length = remaining.min(CHUNK_SIZE);
+        for byte in &mut chunk[..chunk_length] {
+            // Ultimately, matches reference implementation logic as of 2023-08-08,
+            // which is:
+            // ```js
+            // entropy = entropy + Math.floor(random() * 36).toString(36);
+            // ```
+            *byte = BASE_36_DIGITS[rng.random_range(0..BASE_36_DIGITS.len())];
+        }
+        hasher.update(&chunk[..chunk_length]);
+        remaining -= chunk_length;
+    }
+}
+
+/// Returns a hasher which has been fed the timestamp, the first input for
+/// every CUID.
+fn time_hasher(time: u128) -> Sha3_512 {
+    // Note: the reference implementation converts the timestamp to a
+    // base36 number prior to hashing it. I see no reason why the
+    // alternative representation would improve the quality of the hash.
+    // Avoiding it saves us String allocations and radix conversion.
+    let mut hasher = Sha3_512::new();
+    hasher.update(time.to_be_bytes());
+    hasher
 }
 
 /// Retrieves and increments the counter value.
+#[cfg(feature = "std")]
 #[inline]
 fn get_count() -> u64 {
     COUNTER.with(|cell| cell.replace_with(|counter| counter.wrapping_add(1)))
 }
 
-/// Retrieves the thread-local fingerprint.
+/// Retrieves the counter value to use when no counter was provided.
+#[cfg(feature = "std")]
 #[inline]
-fn get_fingerprint() -> String {
-    FINGERPRINT.with(|x| x.clone())
+fn default_count(_rng: &mut (impl RngExt + ?Sized)) -> u64 {
+    get_count()
+}
+
+/// Retrieves the counter value to use when no counter was provided.
+///
+/// Without thread-locals, a random counter value is used.
+#[cfg(not(feature = "std"))]
+#[inline]
+fn default_count(rng: &mut (impl RngExt + ?Sized)) -> u64 {
+    rng.random()
+}
+
+/// Feeds the fingerprint to use when no fingerprinter was provided into the
+/// hasher.
+#[cfg(feature = "std")]
+#[inline]
+fn hash_default_fingerprint(hasher: &mut Sha3_512, _rng: &mut (impl RngExt + ?Sized)) {
+    FINGERPRINT.with_borrow(|fingerprint| hasher.update(fingerprint));
+}
+
+/// Feeds the fingerprint to use when no fingerprinter was provided into the
+/// hasher.
+///
+/// Without thread-locals, a random fingerprint is used.
+#[cfg(not(feature = "std"))]
+#[inline]
+fn hash_default_fingerprint(hasher: &mut Sha3_512, rng: &mut (impl RngExt + ?Sized)) {
+    hasher.update(rng.random::<[u8; 32]>());
+}
+
+/// Retrieves the current time from the system clock, if there is one.
+#[inline]
+fn default_timestamp() -> Result<u128, Error> {
+    #[cfg(feature = "std")]
+    return Ok(SystemClock.try_now_millis()?);
+    #[cfg(not(feature = "std"))]
+    return Err(Error::MissingClock);
 }
 
 /// Retrieves the current thread's ID.
+#[cfg(feature = "std")]
 fn get_thread_id() -> u64 {
     // ThreadId doesn't implement debug or display, but it does implement Hash,
     // so we can get the hash value to use in our fingerprint.
@@ -315,152 +537,1050 @@ fn get_thread_id() -> u64 {
 /// ```
 pub struct CuidConstructor {
     length: u16,
-    counter: fn() -> u64,
-    fingerprinter: fn() -> String,
+    /// When `None`, the thread-local counter is used, or the seeded counter
+    /// if the constructor is seeded.
+    counter: Option<Box<dyn CuidCounter>>,
+    /// Takes precedence over `counter`. Kept separately so that it can be set
+    /// in a `const fn`, which cannot drop a boxed counter.
+    counter_fn: Option<fn() -> u64>,
+    /// When `None`, the thread-local fingerprint is used, or the seeded
+    /// fingerprint if the constructor is seeded. This allows us to hash it in
+    /// place, rather than cloning it for every ID.
+    fingerprinter: Option<Box<dyn Fingerprinter>>,
+    /// Takes precedence over `fingerprinter`, as with `counter_fn`.
+    fingerprinter_fn: Option<fn() -> String>,
+    /// When `None`, the system clock is used, or a fixed timestamp if the
+    /// constructor is seeded. Without the `std` feature, there is no system
+    /// clock, so generation fails unless the constructor is seeded.
+    clock: Option<Box<dyn Clock>>,
+    seeded: Option<Seeded>,
+    /// Prepended to every ID, followed by the separator.
+    prefix: Option<&'static str>,
+    separator: char,
+    alphabet: Alphabet,
 }
 impl CuidConstructor {
     /// Creates a new constructor with default settings.
     pub const fn new() -> Self {
         Self {
             length: DEFAULT_LENGTH as u16,
-            counter: get_count,
-            fingerprinter: get_fingerprint,
+            counter: None,
+            counter_fn: None,
+            fingerprinter: None,
+            fingerprinter_fn: None,
+            clock: None,
+            seeded: None,
+            prefix: None,
+            separator: DEFAULT_SEPARATOR,
+            alphabet: Alphabet::BASE36,
         }
     }
 
     /// Returns a new constructor that will generate CUIDs with the specified length.
     ///
+    /// Any length from 2 up to `u16::MAX` is supported, although only IDs of
+    /// up to 32 characters pass [`is_cuid2()`]. Longer IDs may be used as
+    /// opaque tokens:
+    ///
+    /// ```
+    /// use cuid2::CuidConstructor;
+    ///
+    /// let token = CuidConstructor::new().with_length(200).create_id();
+    /// assert_eq!(200, token.len());
+    /// ```
+    ///
     /// # Panics
     ///
-    /// Panics if `length` is less than 2.
+    /// Panics if `length` is less than 2. See
+    /// [`try_with_length()`](Self::try_with_length) for a non-panicking
+    /// alternative.
     ///
-    pub const fn with_length(self, length: u16) -> Self {
-        if length < 2 {
+    pub const fn with_length(mut self, length: u16) -> Self {
+        if check_length(length).is_err() {
             panic!("CUID length must be at least 2")
         }
-        Self { length, ..self }
+        self.length = length;
+        self
+    }
+
+    /// Returns a new constructor that will generate CUIDs with the specified
+    /// length, or an error if the length is invalid.
+    ///
+    /// ```
+    /// use cuid2::{CuidConstructor, Error};
+    ///
+    /// assert!(CuidConstructor::new().try_with_length(16).is_ok());
+    /// assert_eq!(
+    ///     Some(Error::InvalidLength(1)),
+    ///     CuidConstructor::new().try_with_length(1).err()
+    /// );
+    /// ```
+    pub fn try_with_length(mut self, length: u16) -> Result<Self, Error> {
+        self.try_set_length(length)?;
+        Ok(self)
+    }
+
+    /// Returns a new constructor with the specified counter.
+    ///
+    /// Any closure returning a `u64` may be used as a counter. See
+    /// [`CuidCounter`] for other options.
+    ///
+    /// ```
+    /// use std::sync::atomic::{AtomicU64, Ordering};
+    ///
+    /// use cuid2::CuidConstructor;
+    ///
+    /// let count = AtomicU64::new(1000);
+    /// let constructor = CuidConstructor::new()
+    ///     .with_counter(move || count.fetch_add(2, Ordering::Relaxed));
+    /// ```
+    pub fn with_counter(mut self, counter: impl CuidCounter + 'static) -> Self {
+        self.set_counter(counter);
+        self
+    }
+
+    /// Returns a new constructor with the specified fingerprinter.
+    ///
+    /// Any closure returning a `String` may be used as a fingerprinter, as
+    /// may a fixed `String`. See [`Fingerprinter`] for details.
+    pub fn with_fingerprinter(mut self, fingerprinter: impl Fingerprinter + 'static) -> Self {
+        self.set_fingerprinter(fingerprinter);
+        self
     }
 
     /// Returns a new constructor with the specified counter function.
-    pub const fn with_counter(self, counter: fn() -> u64) -> Self {
-        Self { counter, ..self }
+    ///
+    /// Unlike [`with_counter()`](Self::with_counter), this is a `const fn`, so
+    /// it may be used for a constructor in a `static`:
+    ///
+    /// ```
+    /// use std::sync::atomic::{AtomicU64, Ordering};
+    ///
+    /// use cuid2::CuidConstructor;
+    ///
+    /// fn next_count() -> u64 {
+    ///     static COUNT: AtomicU64 = AtomicU64::new(0);
+    ///     COUNT.fetch_add(1, Ordering::Relaxed)
+    /// }
+    ///
+    /// static CONSTRUCTOR: CuidConstructor = CuidConstructor::new().with_counter_fn(next_count);
+    /// assert!(cuid2::is_cuid2(CONSTRUCTOR.create_id()));
+    /// ```
+    pub const fn with_counter_fn(mut self, counter: fn() -> u64) -> Self {
+        self.counter_fn = Some(counter);
+        self
     }
 
     /// Returns a new constructor with the specified fingerprinter function.
-    pub const fn with_fingerprinter(self, fingerprinter: fn() -> String) -> Self {
-        Self {
-            fingerprinter,
-            ..self
+    ///
+    /// Like [`with_counter_fn()`](Self::with_counter_fn), this is a
+    /// `const fn`.
+    pub const fn with_fingerprinter_fn(mut self, fingerprinter: fn() -> String) -> Self {
+        self.fingerprinter_fn = Some(fingerprinter);
+        self
+    }
+
+    /// Returns a new constructor that will use the specified clock to
+    /// timestamp CUIDs.
+    ///
+    /// Any closure returning a `u128` number of milliseconds since the Unix
+    /// epoch may be used as a clock.
+    ///
+    /// ```
+    /// use cuid2::CuidConstructor;
+    ///
+    /// let constructor = CuidConstructor::new().with_clock(|| 1_700_000_000_000);
+    /// assert!(cuid2::is_cuid2(constructor.create_id()));
+    /// ```
+    pub fn with_clock(mut self, clock: impl Clock + 'static) -> Self {
+        self.set_clock(clock);
+        self
+    }
+
+    /// Returns a new constructor that deterministically generates CUIDs from
+    /// the specified seed.
+    ///
+    /// Two seeded constructors with the same seed and settings will always
+    /// generate the same sequence of CUIDs, which is useful for snapshot tests
+    /// and recorded fixtures. The random number generator, counter, and
+    /// fingerprint are all derived from the seed, and the timestamp is fixed
+    /// at the Unix epoch. A counter, fingerprinter, or clock provided via
+    /// [`with_counter()`](Self::with_counter),
+    /// [`with_fingerprinter()`](Self::with_fingerprinter), or
+    /// [`with_clock()`](Self::with_clock) takes precedence over the seeded
+    /// one.
+    ///
+    /// The sequence is only reproducible if IDs are generated in the same
+    /// order, so a seeded constructor shared between threads will produce the
+    /// same set of IDs, but not necessarily in the same order.
+    ///
+    /// Seeded CUIDs are entirely predictable, and so should never be used
+    /// outside of tests.
+    ///
+    /// ```
+    /// use cuid2::CuidConstructor;
+    ///
+    /// let first = CuidConstructor::new().with_seed(42);
+    /// let second = CuidConstructor::new().with_seed(42);
+    ///
+    /// assert_eq!(first.create_id(), second.create_id());
+    /// assert_eq!(first.create_id(), second.create_id());
+    /// ```
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.set_seed(seed);
+        self
+    }
+
+    /// Returns a new constructor that will generate CUIDs starting with the
+    /// specified prefix and a separator, which is `_` unless set with
+    /// [`with_separator()`](Self::with_separator).
+    ///
+    /// Prefixes make IDs self-describing, e.g. `usr_` for users or `ord_` for
+    /// orders. The rest of the ID is an ordinary CUID2 of the constructor's
+    /// length, so prefixed IDs do not pass [`is_cuid2()`], but can be checked
+    /// with [`is_valid()`](Self::is_valid). See [`define_cuid!`] for a
+    /// distinct type per prefix.
+    ///
+    /// ```
+    /// use cuid2::CuidConstructor;
+    ///
+    /// let users = CuidConstructor::new().with_prefix("usr");
+    /// let id = users.create_id();
+    /// assert!(id.starts_with("usr_"));
+    /// assert_eq!(28, id.len());
+    /// assert!(users.is_valid(&id));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `prefix` is empty or contains anything other than ASCII
+    /// letters and digits. See
+    /// [`try_with_prefix()`](Self::try_with_prefix) for a non-panicking
+    /// alternative.
+    pub const fn with_prefix(mut self, prefix: &'static str) -> Self {
+        if check_prefix(prefix).is_err() {
+            panic!("CUID prefix must be non-empty ASCII letters and digits")
+        }
+        self.prefix = Some(prefix);
+        self
+    }
+
+    /// Returns a new constructor that will generate CUIDs starting with the
+    /// specified prefix, or an error if the prefix is invalid.
+    ///
+    /// See [`with_prefix()`](Self::with_prefix) for details.
+    pub fn try_with_prefix(mut self, prefix: &'static str) -> Result<Self, Error> {
+        self.try_set_prefix(prefix)?;
+        Ok(self)
+    }
+
+    /// Returns a new constructor that will separate the prefix from the rest
+    /// of each CUID with the specified character.
+    ///
+    /// ```
+    /// use cuid2::CuidConstructor;
+    ///
+    /// let id = CuidConstructor::new().with_prefix("ord").with_separator('-').create_id();
+    /// assert!(id.starts_with("ord-"));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `separator` is not ASCII punctuation, since it must not be
+    /// mistaken for part of the prefix or the rest of the ID.
+    pub const fn with_separator(mut self, separator: char) -> Self {
+        if check_separator(separator).is_err() {
+            panic!("CUID prefix separator must be ASCII punctuation")
         }
+        self.separator = separator;
+        self
+    }
+
+    /// Returns a new constructor that will encode CUIDs with the specified
+    /// alphabet, rather than lowercase letters and digits.
+    ///
+    /// The constructor's [`is_valid()`](Self::is_valid) and
+    /// [`validate()`](Self::validate) check IDs against the alphabet, but
+    /// IDs with any other alphabet than [`Alphabet::BASE36`] do not pass
+    /// [`is_cuid2()`].
+    ///
+    /// ```
+    /// use cuid2::{Alphabet, CuidConstructor};
+    ///
+    /// let constructor = CuidConstructor::new().with_alphabet(Alphabet::UNAMBIGUOUS);
+    /// let id = constructor.create_id();
+    /// assert!(!id.contains(['0', '1', 'i', 'l', 'o']));
+    /// assert!(constructor.is_valid(&id));
+    /// ```
+    pub const fn with_alphabet(mut self, alphabet: Alphabet) -> Self {
+        self.alphabet = alphabet;
+        self
     }
 
     /// Sets the length for CUIDs generated by this constrctor.
     ///
     /// # Panics
     ///
-    /// Panics if `length` is less than 2.
+    /// Panics if `length` is less than 2. See
+    /// [`try_set_length()`](Self::try_set_length) for a non-panicking
+    /// alternative.
     ///
     pub fn set_length(&mut self, length: u16) {
-        if length < 2 {
+        if check_length(length).is_err() {
             panic!("CUID length must be at least 2")
         }
         self.length = length;
     }
 
-    /// Sets the counter function for this constructor.
-    pub fn set_counter(&mut self, counter: fn() -> u64) {
-        self.counter = counter;
+    /// Sets the length for CUIDs generated by this constructor, or returns
+    /// an error if the length is invalid, leaving the constructor unchanged.
+    pub fn try_set_length(&mut self, length: u16) -> Result<(), Error> {
+        check_length(length)?;
+        self.length = length;
+        Ok(())
+    }
+
+    /// Sets the counter for this constructor.
+    pub fn set_counter(&mut self, counter: impl CuidCounter + 'static) {
+        self.counter = Some(Box::new(counter));
+        self.counter_fn = None;
+    }
+
+    /// Sets the fingerprinter for this constructor.
+    pub fn set_fingerprinter(&mut self, fingerprinter: impl Fingerprinter + 'static) {
+        self.fingerprinter = Some(Box::new(fingerprinter));
+        self.fingerprinter_fn = None;
+    }
+
+    /// Sets the clock for this constructor.
+    pub fn set_clock(&mut self, clock: impl Clock + 'static) {
+        self.clock = Some(Box::new(clock));
+    }
+
+    /// Sets a seed for deterministic generation of CUIDs, restarting the
+    /// sequence if the constructor was already seeded.
+    ///
+    /// See [`with_seed()`](Self::with_seed) for details.
+    pub fn set_seed(&mut self, seed: u64) {
+        self.seeded = Some(Seeded::new(seed));
+    }
+
+    /// Sets the prefix for CUIDs generated by this constructor.
+    ///
+    /// See [`with_prefix()`](Self::with_prefix) for details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `prefix` is empty or contains anything other than ASCII
+    /// letters and digits. See
+    /// [`try_set_prefix()`](Self::try_set_prefix) for a non-panicking
+    /// alternative.
+    pub fn set_prefix(&mut self, prefix: &'static str) {
+        if check_prefix(prefix).is_err() {
+            panic!("CUID prefix must be non-empty ASCII letters and digits")
+        }
+        self.prefix = Some(prefix);
+    }
+
+    /// Sets the prefix for CUIDs generated by this constructor, or returns
+    /// an error if the prefix is invalid, leaving the constructor unchanged.
+    pub fn try_set_prefix(&mut self, prefix: &'static str) -> Result<(), Error> {
+        check_prefix(prefix)?;
+        self.prefix = Some(prefix);
+        Ok(())
     }
 
-    /// Sets the fingerperinter function for this constructor.
-    pub fn set_fingerprinter(&mut self, fingerprinter: fn() -> String) {
-        self.fingerprinter = fingerprinter;
+    /// Sets the separator between the prefix and the rest of each CUID.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `separator` is not ASCII punctuation.
+    pub fn set_separator(&mut self, separator: char) {
+        if check_separator(separator).is_err() {
+            panic!("CUID prefix separator must be ASCII punctuation")
+        }
+        self.separator = separator;
+    }
+
+    /// Sets the alphabet for CUIDs generated by this constructor.
+    ///
+    /// See [`with_alphabet()`](Self::with_alphabet) for details.
+    pub fn set_alphabet(&mut self, alphabet: Alphabet) {
+        self.alphabet = alphabet;
     }
 
     /// Creates a new CUID.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this constructor's clock reports a time before the Unix
+    /// epoch. See [`try_create_id()`](Self::try_create_id) for a
+    /// non-panicking alternative.
+    #[cfg(feature = "std")]
     #[inline]
     pub fn create_id(&self) -> String {
-        // Note: the reference implementation converts this to a base36
-        // number prior to hashing it. I see no reason why the alternative
-        // representation would improve the quality of the hash. Avoiding
-        // it saves us String allocations and radix conversion.
-        let time = get_timestamp();
+        self.try_create_id()
+            .unwrap_or_else(|err| panic!("Failed to create CUID: {err}"))
+    }
+
+    /// Creates a new CUID, or returns an error if one cannot be generated.
+    ///
+    /// ```
+    /// use cuid2::CuidConstructor;
+    ///
+    /// let id = CuidConstructor::new().try_create_id().unwrap();
+    /// assert!(cuid2::is_cuid2(id));
+    /// ```
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn try_create_id(&self) -> Result<String, Error> {
+        self.try_create_id_with_rng(&mut rand::rng())
+    }
+
+    /// Creates a new CUID, using the provided random number generator.
+    ///
+    /// This is the only way to generate CUIDs without the `std` feature. If
+    /// the constructor is seeded, its seeded RNG is used instead.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this constructor's clock reports a time before the Unix
+    /// epoch, or, without the `std` feature, if it has no clock. See
+    /// [`try_create_id_with_rng()`](Self::try_create_id_with_rng) for a
+    /// non-panicking alternative.
+    #[inline]
+    pub fn create_id_with_rng(&self, rng: &mut (impl Rng + ?Sized)) -> String {
+        self.try_create_id_with_rng(rng)
+            .unwrap_or_else(|err| panic!("Failed to create CUID: {err}"))
+    }
+
+    /// Creates a new CUID using the provided random number generator, or
+    /// returns an error if one cannot be generated.
+    ///
+    /// See [`create_id_with_rng()`](Self::create_id_with_rng) for details.
+    #[inline]
+    pub fn try_create_id_with_rng(&self, rng: &mut (impl Rng + ?Sized)) -> Result<String, Error> {
+        let (first_letter, body) = self.generate(rng)?;
+        Ok(self.assemble(first_letter, body))
+    }
 
+    /// Creates `count` new CUIDs.
+    ///
+    /// This is faster than calling [`create_id()`](Self::create_id) in a
+    /// loop, since setup is shared between the IDs: the random number
+    /// generator is retrieved only once, and the timestamp is only hashed
+    /// again when the clock reports a new millisecond. The clock is read for
+    /// every ID, so each gets the same timestamp as it would from
+    /// `create_id()`.
+    ///
+    /// The fingerprinter, however, is called only once per batch, and its
+    /// fingerprint used for every ID. A fingerprinter which returns a
+    /// different value on each call will therefore produce different IDs
+    /// than repeated calls to `create_id()`.
+    ///
+    /// ```
+    /// use cuid2::CuidConstructor;
+    ///
+    /// let ids = CuidConstructor::new().create_ids(1000);
+    /// assert_eq!(1000, ids.len());
+    /// assert!(ids.iter().all(cuid2::is_cuid2));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if this constructor's clock reports a time before the Unix
+    /// epoch. See [`try_create_ids()`](Self::try_create_ids) for a
+    /// non-panicking alternative.
+    #[cfg(feature = "std")]
+    pub fn create_ids(&self, count: usize) -> Vec<String> {
+        let mut ids = Vec::with_capacity(count);
+        self.extend_with_ids(&mut ids, count);
+        ids
+    }
+
+    /// Creates `count` new CUIDs, or returns an error if they cannot be
+    /// generated.
+    ///
+    /// See [`create_ids()`](Self::create_ids) for details.
+    #[cfg(feature = "std")]
+    pub fn try_create_ids(&self, count: usize) -> Result<Vec<String>, Error> {
+        let mut batch = Batch::on_current_thread(self);
+        let mut rng = rand::rng();
+        (0..count).map(|_| batch.try_next(&mut rng)).collect()
+    }
+
+    /// Adds `count` new CUIDs to a collection.
+    ///
+    /// Like [`create_ids()`](Self::create_ids), this shares setup between the
+    /// IDs, and may be used to fill any collection implementing [`Extend`].
+    ///
+    /// ```
+    /// use std::collections::HashSet;
+    ///
+    /// use cuid2::CuidConstructor;
+    ///
+    /// let mut ids = HashSet::new();
+    /// CuidConstructor::new().extend_with_ids(&mut ids, 1000);
+    /// assert_eq!(1000, ids.len());
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if this constructor's clock reports a time before the Unix
+    /// epoch. See [`try_extend_with_ids()`](Self::try_extend_with_ids) for a
+    /// non-panicking alternative.
+    #[cfg(feature = "std")]
+    pub fn extend_with_ids(&self, ids: &mut impl Extend<String>, count: usize) {
+        let mut batch = Batch::on_current_thread(self);
         let mut rng = rand::rng();
-        let entropy = create_entropy(self.length, &mut rng);
+        ids.extend((0..count).map(|_| {
+            batch
+                .try_next(&mut rng)
+                .unwrap_or_else(|err| panic!("Failed to create CUID: {err}"))
+        }));
+    }
 
-        let count = (self.counter)();
-        let fingerprint = (self.fingerprinter)();
+    /// Adds `count` new CUIDs to a collection, or returns an error if they
+    /// cannot be generated.
+    ///
+    /// If an error occurs, the IDs generated before it are left in the
+    /// collection. See [`extend_with_ids()`](Self::extend_with_ids) for
+    /// details.
+    #[cfg(feature = "std")]
+    pub fn try_extend_with_ids(
+        &self,
+        ids: &mut impl Extend<String>,
+        count: usize,
+    ) -> Result<(), Error> {
+        let mut batch = Batch::on_current_thread(self);
+        let mut rng = rand::rng();
+        let mut error = None;
+        ids.extend((0..count).map_while(|_| {
+            batch
+                .try_next(&mut rng)
+                .map_err(|err| error = Some(err))
+                .ok()
+        }));
+        error.map_or(Ok(()), Err)
+    }
 
-        // Construct the main part of the ID body by hashing the various inputs
-        let id_body = hash(
-            [
-                &time.to_be_bytes(),
-                entropy.as_bytes(),
-                &count.to_be_bytes(),
-                fingerprint.as_bytes(),
-            ],
-            // The hash should be the desired total length minus 1 character
-            // for the starting char.
-            self.length - 1,
-        );
+    /// Returns an iterator which yields new CUIDs forever.
+    ///
+    /// The iterator reuses setup between IDs where it can, so it is slightly
+    /// faster than calling [`create_id()`](Self::create_id) repeatedly, in
+    /// the same way as [`create_ids()`](Self::create_ids).
+    /// `&CuidConstructor` also implements [`IntoIterator`], yielding the same
+    /// iterator.
+    ///
+    /// ```
+    /// use cuid2::CuidConstructor;
+    ///
+    /// let rows = ["alice", "bob", "carol"];
+    /// let constructor = CuidConstructor::new();
+    /// for (id, name) in constructor.iter().zip(rows) {
+    ///     assert!(cuid2::is_cuid2(id));
+    /// }
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Iteration panics if this constructor's clock reports a time before the
+    /// Unix epoch. See [`try_iter()`](Self::try_iter) for a non-panicking
+    /// alternative.
+    #[cfg(feature = "std")]
+    pub fn iter(&self) -> Cuids<'_> {
+        Cuids::new(self)
+    }
+
+    /// Returns an iterator which yields new CUIDs forever, or an error for
+    /// each ID which cannot be generated.
+    ///
+    /// ```
+    /// use cuid2::CuidConstructor;
+    ///
+    /// let constructor = CuidConstructor::new();
+    /// let ids = constructor.try_iter().take(3).collect::<Result<Vec<_>, _>>();
+    /// assert_eq!(3, ids.unwrap().len());
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn try_iter(&self) -> TryCuids<'_> {
+        TryCuids::new(self)
+    }
+
+    /// Returns a [`Stream`](futures_core::Stream) which yields new CUIDs
+    /// forever.
+    ///
+    /// The stream is always ready, and is `Send`. See [`CuidStream`] for
+    /// details.
+    ///
+    /// # Panics
+    ///
+    /// Polling panics if this constructor's clock reports a time before the
+    /// Unix epoch. See [`try_stream()`](Self::try_stream) for a non-panicking
+    /// alternative.
+    #[cfg(feature = "futures")]
+    pub fn stream(&self) -> CuidStream<'_> {
+        CuidStream::new(self)
+    }
+
+    /// Returns a [`Stream`](futures_core::Stream) which yields new CUIDs
+    /// forever, or an error for each ID which cannot be generated.
+    #[cfg(feature = "futures")]
+    pub fn try_stream(&self) -> TryCuidStream<'_> {
+        TryCuidStream::new(self)
+    }
 
-        let letter_idx = rng.random_range(0..STARTING_CHARS.len());
-        let first_letter = STARTING_CHARS[letter_idx];
+    /// Creates a new CUID, stored inline rather than on the heap.
+    ///
+    /// ```
+    /// use cuid2::CuidConstructor;
+    ///
+    /// let id = CuidConstructor::new().create_inline();
+    /// assert!(cuid2::is_cuid2(id));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if this constructor's length, including any prefix, is greater
+    /// than 32, the capacity of an [`InlineId`], or if its clock reports a
+    /// time before the Unix epoch. See
+    /// [`try_create_inline()`](Self::try_create_inline) for a non-panicking
+    /// alternative.
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn create_inline(&self) -> InlineId {
+        self.try_create_inline()
+            .unwrap_or_else(|err| panic!("Failed to create CUID: {err}"))
+    }
+
+    /// Creates a new CUID, stored inline rather than on the heap, or returns
+    /// an error if one cannot be generated.
+    ///
+    /// ```
+    /// use cuid2::{CuidConstructor, Error};
+    ///
+    /// assert!(CuidConstructor::new().try_create_inline().is_ok());
+    /// assert_eq!(
+    ///     Some(Error::TooLongToInline(40)),
+    ///     CuidConstructor::new().with_length(40).try_create_inline().err()
+    /// );
+    /// ```
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn try_create_inline(&self) -> Result<InlineId, Error> {
+        let length = self.prefix_len() + usize::from(self.length);
+        if length > INLINE_CAPACITY {
+            return Err(Error::TooLongToInline(length));
+        }
 
-        let mut id = String::with_capacity(id_body.len() + 1);
+        let (first_letter, body) = self.generate(&mut rand::rng())?;
+
+        // Encode the body straight into the inline buffer, without
+        // allocating
+        let mut id = InlineId::new();
+        if let Some(prefix) = self.prefix {
+            id.push_str(prefix);
+            id.push(self.separator);
+        }
+        id.push(first_letter.into());
+        encode_digest(body, self.body_len(), &self.alphabet, |ch| id.push(ch));
+        Ok(id)
+    }
+
+    /// Joins a starting character and the digits of a hashed body into a
+    /// CUID, after this constructor's prefix, if it has one.
+    fn assemble(&self, first_letter: u8, body: Sha3_512) -> String {
+        let mut id = String::with_capacity(self.prefix_len() + usize::from(self.length));
+        if let Some(prefix) = self.prefix {
+            id.push_str(prefix);
+            id.push(self.separator);
+        }
         id.push(first_letter.into());
-        id.push_str(&id_body);
+        encode_digest(body, self.body_len(), &self.alphabet, |ch| id.push(ch));
         id
     }
+
+    /// Returns the length of the body of this constructor's CUIDs, i.e.
+    /// everything but the prefix and the starting character.
+    #[inline]
+    fn body_len(&self) -> usize {
+        usize::from(self.length) - 1
+    }
+
+    /// Returns the length of this constructor's prefix and separator.
+    #[inline]
+    fn prefix_len(&self) -> usize {
+        // The separator is always ASCII, so it is one byte long
+        self.prefix.map_or(0, |prefix| prefix.len() + 1)
+    }
+
+    /// Generates the starting character of a new CUID, and a hasher which has
+    /// been fed the inputs for its body.
+    #[inline]
+    fn generate(&self, rng: &mut (impl RngExt + ?Sized)) -> Result<(u8, Sha3_512), Error> {
+        let time = self.current_time()?;
+        let fingerprint = self.fingerprinter().map(|f| f.fingerprint());
+        Ok(self.generate_at(rng, time_hasher(time), fingerprint.as_deref()))
+    }
+
+    /// Generates the starting character of a new CUID, and a hasher which has
+    /// been fed the inputs for its body, given a hasher which has already
+    /// been fed the timestamp and the output of this constructor's
+    /// fingerprinter, if it has one.
+    #[inline]
+    fn generate_at(
+        &self,
+        rng: &mut (impl RngExt + ?Sized),
+        hasher: Sha3_512,
+        fingerprint: Option<&str>,
+    ) -> (u8, Sha3_512) {
+        match &self.seeded {
+            None => {
+                let body = self.create_body(rng, hasher, None, fingerprint);
+                (self.alphabet.random_letter(rng), body)
+            }
+            Some(seeded) => {
+                let (mut rng, count) = seeded.next();
+                let fingerprint = fingerprint.unwrap_or(seeded.fingerprint());
+                let body = self.create_body(&mut rng, hasher, Some(count), Some(fingerprint));
+                (self.alphabet.random_letter(&mut rng), body)
+            }
+        }
+    }
+
+    /// Returns this constructor's counter, if it has one.
+    #[inline]
+    fn counter(&self) -> Option<&dyn CuidCounter> {
+        match &self.counter_fn {
+            Some(counter) => Some(counter),
+            None => self.counter.as_deref(),
+        }
+    }
+
+    /// Returns this constructor's fingerprinter, if it has one.
+    #[inline]
+    fn fingerprinter(&self) -> Option<&dyn Fingerprinter> {
+        match &self.fingerprinter_fn {
+            Some(fingerprinter) => Some(fingerprinter),
+            None => self.fingerprinter.as_deref(),
+        }
+    }
+
+    /// Retrieves the current time from this constructor's clock, if it has
+    /// one, or from the system clock (or the fixed seeded timestamp)
+    /// otherwise.
+    #[inline]
+    fn current_time(&self) -> Result<u128, Error> {
+        match (&self.clock, &self.seeded) {
+            (Some(clock), _) => Ok(clock.try_now_millis()?),
+            (None, Some(_)) => Ok(SEEDED_TIMESTAMP),
+            (None, None) => default_timestamp(),
+        }
+    }
+
+    /// Hashes the inputs for a new CUID, returning the hasher, whose digest
+    /// is encoded as the body of the ID, i.e. everything but the starting
+    /// character.
+    ///
+    /// `hasher` must already have been fed the timestamp. `count` is the
+    /// counter value to use if this constructor is seeded and no counter was
+    /// provided, and `fingerprint` the fingerprint to use if there is one
+    /// other than the thread-local default.
+    fn create_body(
+        &self,
+        rng: &mut (impl RngExt + ?Sized),
+        mut hasher: Sha3_512,
+        count: Option<u64>,
+        fingerprint: Option<&str>,
+    ) -> Sha3_512 {
+        let count = match (self.counter(), count) {
+            (Some(counter), _) => counter.next_count(),
+            (None, Some(count)) => count,
+            (None, None) => default_count(rng),
+        };
+
+        // Construct the main part of the ID body by hashing the various inputs
+        hash_entropy(&mut hasher, self.length, rng);
+        hasher.update(count.to_be_bytes());
+        match fingerprint {
+            Some(fingerprint) => hasher.update(fingerprint),
+            None => hash_default_fingerprint(&mut hasher, rng),
+        }
+
+        hasher
+    }
+
+    /// Creates a new CUID as a [`Cuid2`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if this constructor has a prefix, an alphabet other than
+    /// [`Alphabet::BASE36`], or a length greater than 32, since such IDs are
+    /// not valid `Cuid2`s. Use [`define_cuid!`] to define a type for prefixed
+    /// IDs instead.
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn create_id_typed(&self) -> Cuid2 {
+        assert!(
+            self.prefix.is_none(),
+            "prefixed CUIDs cannot be stored as a Cuid2"
+        );
+        assert!(
+            self.alphabet == Alphabet::BASE36,
+            "CUIDs with a custom alphabet cannot be stored as a Cuid2"
+        );
+        assert!(
+            self.length <= u16::from(BIG_LENGTH),
+            "CUIDs longer than {BIG_LENGTH} characters cannot be stored as a Cuid2"
+        );
+        Cuid2::from_string_unchecked(self.create_id())
+    }
+
+    /// Returns whether a string could have been generated by this
+    /// constructor.
+    ///
+    /// Unlike [`is_cuid2()`], which accepts any length from 2 to 32, this
+    /// requires the exact length of this constructor's CUIDs.
+    ///
+    /// ```
+    /// use cuid2::CuidConstructor;
+    ///
+    /// let constructor = CuidConstructor::new().with_length(16);
+    /// assert!(constructor.is_valid(constructor.create_id()));
+    /// assert!(!constructor.is_valid(cuid2::create_id()));
+    /// ```
+    #[inline]
+    pub fn is_valid<S: AsRef<str>>(&self, to_check: S) -> bool {
+        self.validate(to_check).is_ok()
+    }
+
+    /// Checks whether a string could have been generated by this
+    /// constructor, returning the reason if not.
+    ///
+    /// See [`is_valid()`](Self::is_valid) for details.
+    #[inline]
+    pub fn validate<S: AsRef<str>>(&self, to_check: S) -> Result<(), ValidationError> {
+        self.strip_prefix(to_check.as_ref()).map(|_| ())
+    }
+
+    /// Checks whether a string could have been generated by this
+    /// constructor, returning the ID without its prefix and separator if so.
+    ///
+    /// For constructors without a prefix, the ID is returned unchanged.
+    /// Positions and lengths in errors refer to the whole string, including
+    /// the prefix.
+    ///
+    /// ```
+    /// use cuid2::{CuidConstructor, ValidationError};
+    ///
+    /// let users = CuidConstructor::new().with_prefix("usr");
+    /// let id = users.create_id();
+    /// assert_eq!(Ok(&id[4..]), users.strip_prefix(&id));
+    ///
+    /// let orders = CuidConstructor::new().with_prefix("ord");
+    /// assert_eq!(Err(ValidationError::MissingPrefix), orders.strip_prefix(&id));
+    /// ```
+    pub fn strip_prefix<'a>(&self, to_check: &'a str) -> Result<&'a str, ValidationError> {
+        let length = usize::from(self.length);
+        let Some(prefix) = self.prefix else {
+            return validate_cuid2_inner(to_check, length, length, &self.alphabet)
+                .map(|_| to_check);
+        };
+
+        let body = to_check
+            .strip_prefix(prefix)
+            .and_then(|rest| rest.strip_prefix(self.separator))
+            .ok_or(ValidationError::MissingPrefix)?;
+        validate_cuid2_inner(body, length, length, &self.alphabet)
+            .map(|_| body)
+            .map_err(|err| err.offset(self.prefix_len()))
+    }
+}
+
+/// Checks that `prefix` is a valid CUID prefix: one or more ASCII letters or
+/// digits.
+const fn check_prefix(prefix: &str) -> Result<(), Error> {
+    let bytes = prefix.as_bytes();
+    if bytes.is_empty() {
+        return Err(Error::InvalidPrefix);
+    }
+    let mut idx = 0;
+    while idx < bytes.len() {
+        if !bytes[idx].is_ascii_alphanumeric() {
+            return Err(Error::InvalidPrefix);
+        }
+        idx += 1;
+    }
+    Ok(())
+}
+
+/// Checks that `separator` is a valid separator between a prefix and the
+/// rest of a CUID. It must be a single byte, and must not be confused with a
+/// character of either.
+const fn check_separator(separator: char) -> Result<(), Error> {
+    if separator.is_ascii_punctuation() {
+        Ok(())
+    } else {
+        Err(Error::InvalidSeparator(separator))
+    }
+}
+
+/// Checks that `length` is a valid CUID length.
+const fn check_length(length: u16) -> Result<(), Error> {
+    if length < MIN_LENGTH {
+        Err(Error::InvalidLength(length))
+    } else {
+        Ok(())
+    }
 }
+
 impl Default for CuidConstructor {
     fn default() -> Self {
         Self::new()
     }
 }
 
+#[cfg(feature = "std")]
+impl<'a> IntoIterator for &'a CuidConstructor {
+    type Item = String;
+    type IntoIter = Cuids<'a>;
+
+    fn into_iter(self) -> Cuids<'a> {
+        self.iter()
+    }
+}
+
 /// Use a static constructor for create_id() so that we don't need to pay the
 /// (minimal, probably trivial) cost of constructor creation when called via
 /// `create_id()`.
+#[cfg(feature = "std")]
 static DEFAULT_CONSTRUCTOR: CuidConstructor = CuidConstructor::new();
 
 const SLUG_LENGTH: u16 = 10;
 
+/// The separator between a prefix and the rest of a CUID, unless otherwise
+/// specified.
+const DEFAULT_SEPARATOR: char = '_';
+
+#[cfg(feature = "std")]
 static SLUG_CONSTRUCTOR: CuidConstructor = CuidConstructor::new().with_length(SLUG_LENGTH);
 
 /// Creates a new CUID.
+#[cfg(feature = "std")]
 #[inline]
 pub fn create_id() -> String {
     DEFAULT_CONSTRUCTOR.create_id()
 }
 
+/// Creates a new CUID, or returns an error if one cannot be generated.
+///
+/// Unlike [`create_id()`], this never panics.
+#[cfg(feature = "std")]
+#[inline]
+pub fn try_create_id() -> Result<String, Error> {
+    DEFAULT_CONSTRUCTOR.try_create_id()
+}
+
+/// Creates a new CUID as a [`Cuid2`].
+#[cfg(feature = "std")]
+#[inline]
+pub fn create_id_typed() -> Cuid2 {
+    DEFAULT_CONSTRUCTOR.create_id_typed()
+}
+
+/// Creates `count` new CUIDs.
+///
+/// See [`CuidConstructor::create_ids()`] for details.
+#[cfg(feature = "std")]
+pub fn create_ids(count: usize) -> Vec<String> {
+    DEFAULT_CONSTRUCTOR.create_ids(count)
+}
+
 /// Creates a new CUID.
 ///
 /// Alias for [`create_id()`], which is the interface defined in the reference
 /// implementation. The `cuid()` interface allows easier drop-in replacement
 /// for crates using the v1 `cuid` crate.
+#[cfg(feature = "std")]
 #[inline]
 pub fn cuid() -> String {
     create_id()
 }
 
 /// Creates a new CUID slug, which is just a CUID with a length of 10 characters.
+#[cfg(feature = "std")]
 #[inline]
 pub fn slug() -> String {
     SLUG_CONSTRUCTOR.create_id()
 }
 
+/// Reinitializes the current thread's counter, fingerprint, and random number
+/// generator.
+///
+/// These are seeded once per thread and are copied as-is into a child process
+/// by `fork()`, so without reseeding, a parent and its children (or several
+/// children of the same parent) may generate colliding IDs. Call this in the
+/// child immediately after forking, e.g. in a pre-fork server's worker
+/// initialization hook. Since only the forking thread survives a `fork()`,
+/// reseeding it is sufficient.
+///
+/// Constructors with a custom counter, fingerprinter, or seed are unaffected.
+///
+/// # Panics
+///
+/// Panics if the operating system's random number source fails.
+///
+/// # Examples
+///
+/// ```rust
+/// let before = cuid2::create_id();
+/// cuid2::reseed();
+/// let after = cuid2::create_id();
+/// assert_ne!(before, after);
+/// ```
+#[cfg(feature = "std")]
+pub fn reseed() {
+    rand::rng()
+        .reseed()
+        .expect("failed to reseed the thread-local RNG");
+    let counter_init = random_counter_init();
+    COUNTER_INIT.set(counter_init);
+    COUNTER.set(counter_init);
+    FINGERPRINT.set(fingerprint());
+}
+
 /// Return whether a string looks like it could be a legitimate CUID slug.
 #[inline]
 pub fn is_slug<S: AsRef<str>>(to_check: S) -> bool {
+    validate_slug(to_check).is_ok()
+}
+
+/// Check whether a string looks like it could be a legitimate CUID slug,
+/// returning the reason if not.
+#[inline]
+pub fn validate_slug<S: AsRef<str>>(to_check: S) -> Result<(), ValidationError> {
     const MAX_LENGTH: usize = SLUG_LENGTH as usize;
-    is_cuid2_inner::<S, MAX_LENGTH>(to_check)
+    validate_cuid2_inner(
+        to_check.as_ref(),
+        MIN_LENGTH.into(),
+        MAX_LENGTH,
+        &Alphabet::BASE36,
+    )
 }
 
 #[cfg(test)]
 mod test {
-    use std::{collections::HashSet, thread};
+    #[cfg(feature = "std")]
+    use std::{
+        collections::HashSet,
+        sync::{
+            Arc,
+            atomic::{AtomicU64, Ordering},
+        },
+        thread,
+    };
+
+    use num::bigint;
 
     use super::*;
 
@@ -476,6 +1596,7 @@ mod test {
         };
     }
 
+    #[cfg(feature = "std")]
     #[test]
     fn counter_increments() {
         let start = get_count();
@@ -484,15 +1605,79 @@ mod test {
         // concurrent test may have also incremented
         assert!(next > start);
     }
+    #[cfg(feature = "std")]
     wasm_test!(counter_increments);
 
+    #[cfg(feature = "std")]
+    #[test]
+    fn reseeding() {
+        let fingerprint = FINGERPRINT.with_borrow(String::clone);
+        // seed a known counter value so we can tell that reseeding changes it
+        COUNTER.set(u64::MAX);
+
+        reseed();
+
+        assert_ne!(fingerprint, FINGERPRINT.with_borrow(String::clone));
+        let count = get_count();
+        assert!(count < MAX_COUNTER_INIT);
+        assert_eq!(count, COUNTER_INIT.get());
+        assert!(is_cuid(create_id()));
+    }
+    #[cfg(feature = "std")]
+    wasm_test!(reseeding);
+
+    #[cfg(feature = "std")]
     #[test]
     fn cuid_generation() {
         assert!(is_cuid(cuid()))
     }
+    #[cfg(feature = "std")]
     wasm_test!(cuid_generation);
 
+    #[cfg(feature = "std")]
+    #[test]
+    fn validation_reasons() {
+        assert_eq!(Ok(()), validate_cuid2(create_id()));
+        assert_eq!(Ok(()), validate_slug(slug()));
+        assert_eq!(
+            Err(ValidationError::TooShort { length: 1, min: 2 }),
+            validate_cuid2("a")
+        );
+        assert_eq!(
+            Err(ValidationError::TooLong {
+                length: 33,
+                max: 32
+            }),
+            validate_cuid2("a".repeat(33))
+        );
+        assert_eq!(
+            Err(ValidationError::TooLong {
+                length: 11,
+                max: 10
+            }),
+            validate_slug("a".repeat(11))
+        );
+        assert_eq!(
+            Err(ValidationError::InvalidStart('é')),
+            validate_cuid2("éa")
+        );
+        assert_eq!(
+            Err(ValidationError::InvalidChar {
+                position: 2,
+                character: 'é'
+            }),
+            validate_cuid2("aaéa")
+        );
+        assert_eq!(
+            "invalid character '#' at position 1, expected a character from the CUID's alphabet",
+            validate_cuid2("a#").unwrap_err().to_string()
+        );
+    }
+    #[cfg(feature = "std")]
+    wasm_test!(validation_reasons);
+
     // lesser version of the collisions test for WASM
+    #[cfg(feature = "std")]
     #[wasm_bindgen_test::wasm_bindgen_test]
     fn wasm_collisions() {
         let count = 10_000;
@@ -503,6 +1688,575 @@ mod test {
         assert_eq!(count, cuids.len());
     }
 
+    #[cfg(feature = "std")]
+    #[test]
+    fn inline_generation() {
+        assert!(is_cuid(CuidConstructor::new().create_inline()));
+        assert_eq!(
+            32,
+            CuidConstructor::new().with_length(32).create_inline().len()
+        );
+
+        // Inline IDs are the same as those on the heap
+        let heap = CuidConstructor::new().with_seed(42).with_prefix("usr");
+        let inline = CuidConstructor::new().with_seed(42).with_prefix("usr");
+        for _ in 0..10 {
+            assert_eq!(heap.create_id(), inline.create_inline().as_str());
+        }
+    }
+    #[cfg(feature = "std")]
+    wasm_test!(inline_generation);
+
+    #[cfg(feature = "std")]
+    #[test]
+    #[should_panic]
+    fn inline_generation_too_long() {
+        CuidConstructor::new().with_length(33).create_inline();
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    #[should_panic = "cannot be stored as a Cuid2"]
+    fn typed_generation_too_long() {
+        CuidConstructor::new().with_length(33).create_id_typed();
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn seeded_generation_is_reproducible() {
+        let first = CuidConstructor::new().with_seed(42);
+        let second = CuidConstructor::new().with_seed(42);
+        let other = CuidConstructor::new().with_seed(43);
+        for _ in 0..100 {
+            let id = first.create_id();
+            assert!(is_cuid(&id));
+            assert_eq!(id, second.create_id());
+            assert_ne!(id, other.create_id());
+        }
+    }
+    #[cfg(feature = "std")]
+    wasm_test!(seeded_generation_is_reproducible);
+
+    #[cfg(feature = "std")]
+    /// Seeded IDs must stay the same across runs and releases, or they're not
+    /// much use for snapshot tests.
+    #[test]
+    fn seeded_generation_snapshot() {
+        let constructor = CuidConstructor::new().with_seed(42);
+        assert_eq!("p2qu386dsdkp1ekc980ozf3r", constructor.create_id());
+        assert_eq!("x3cwis8oafmxnslz4kbsynej", constructor.create_id());
+        assert_eq!("alrimr6rwkp3bcgzkw5chvji", constructor.create_id());
+
+        let mut constructor = CuidConstructor::new().with_length(10);
+        constructor.set_seed(42);
+        assert_eq!("qsmh8ph75x", constructor.create_inline().as_str());
+    }
+    #[cfg(feature = "std")]
+    wasm_test!(seeded_generation_snapshot);
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn seeded_generation_with_counter() {
+        let first = CuidConstructor::new().with_seed(1).with_counter(|| 0);
+        let second = CuidConstructor::new().with_seed(1);
+        assert_ne!(first.create_id(), second.create_id());
+    }
+    #[cfg(feature = "std")]
+    wasm_test!(seeded_generation_with_counter);
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn custom_clock() {
+        let first = CuidConstructor::new().with_seed(7).with_clock(|| 1);
+        let second = CuidConstructor::new().with_seed(7).with_clock(|| 2);
+        let third = CuidConstructor::new().with_seed(7).with_clock(|| 2);
+        let second_id = second.create_id();
+        assert_ne!(first.create_id(), second_id);
+        assert_eq!(third.create_id(), second_id);
+    }
+    #[cfg(feature = "std")]
+    wasm_test!(custom_clock);
+
+    #[test]
+    fn rng_generation() {
+        use rand::{SeedableRng, rngs::ChaCha8Rng};
+
+        let constructor = CuidConstructor::new().with_clock(|| 1_700_000_000_000);
+        let mut rng = ChaCha8Rng::seed_from_u64(1);
+        let id = constructor.create_id_with_rng(&mut rng);
+        assert!(is_cuid(&id));
+        assert_ne!(id, constructor.create_id_with_rng(&mut rng));
+
+        // Seeded constructors ignore the provided RNG
+        let seeded = CuidConstructor::new().with_seed(42);
+        assert_eq!(
+            "p2qu386dsdkp1ekc980ozf3r",
+            seeded.try_create_id_with_rng(&mut rng).unwrap()
+        );
+    }
+    wasm_test!(rng_generation);
+
+    #[test]
+    fn caller_supplied_sources() {
+        use core::sync::atomic::AtomicU64;
+
+        use rand::{SeedableRng, rngs::ChaCha8Rng};
+
+        // Everything that would otherwise come from `std` is provided, so
+        // generation is deterministic
+        let constructor = || {
+            CuidConstructor::new()
+                .with_clock(|| 1_700_000_000_000)
+                .with_counter(AtomicU64::new(0))
+                .with_fingerprinter("device-1")
+        };
+        let (first, second) = (constructor(), constructor());
+        let mut first_rng = ChaCha8Rng::seed_from_u64(1);
+        let mut second_rng = ChaCha8Rng::seed_from_u64(1);
+        for _ in 0..3 {
+            let id = first.create_id_with_rng(&mut first_rng);
+            assert!(is_cuid(&id));
+            assert_eq!(Ok(id), second.try_create_id_with_rng(&mut second_rng));
+        }
+
+        let with_prefix = constructor().with_prefix("dev").with_length(10);
+        let id = with_prefix.create_id_with_rng(&mut first_rng);
+        assert!(with_prefix.is_valid(&id), "{id}");
+
+        // Without std, there is no system clock to fall back on
+        #[cfg(not(feature = "std"))]
+        assert_eq!(
+            Err(Error::MissingClock),
+            CuidConstructor::new().try_create_id_with_rng(&mut first_rng)
+        );
+    }
+    wasm_test!(caller_supplied_sources);
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn stateful_hooks() {
+        let count = Arc::new(AtomicU64::new(0));
+        let tenant_count = Arc::clone(&count);
+        let constructor = CuidConstructor::new()
+            .with_counter(move || tenant_count.fetch_add(1, Ordering::Relaxed))
+            .with_fingerprinter(String::from("tenant-a"));
+        assert!(is_cuid(constructor.create_id()));
+        assert!(is_cuid(constructor.create_id()));
+        assert_eq!(2, count.load(Ordering::Relaxed));
+
+        let seeded_a = CuidConstructor::new()
+            .with_seed(3)
+            .with_fingerprinter("tenant-a");
+        let seeded_b = CuidConstructor::new()
+            .with_seed(3)
+            .with_fingerprinter(|| String::from("tenant-b"));
+        assert_ne!(seeded_a.create_id(), seeded_b.create_id());
+    }
+    #[cfg(feature = "std")]
+    wasm_test!(stateful_hooks);
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn const_hooks() {
+        fn fixed_count() -> u64 {
+            7
+        }
+        fn fixed_fingerprint() -> String {
+            String::from("fixed")
+        }
+        static CONSTRUCTOR: CuidConstructor = CuidConstructor::new()
+            .with_counter_fn(fixed_count)
+            .with_fingerprinter_fn(fixed_fingerprint);
+        assert!(is_cuid(CONSTRUCTOR.create_id()));
+
+        // Whichever counter was set last is used
+        let count = Arc::new(AtomicU64::new(0));
+        let constructor = CuidConstructor::new()
+            .with_counter_fn(fixed_count)
+            .with_counter(Arc::clone(&count));
+        constructor.create_id();
+        assert_eq!(1, count.load(Ordering::Relaxed));
+        let constructor = CuidConstructor::new()
+            .with_counter(Arc::clone(&count))
+            .with_counter_fn(fixed_count);
+        constructor.create_id();
+        assert_eq!(1, count.load(Ordering::Relaxed));
+    }
+    #[cfg(feature = "std")]
+    wasm_test!(const_hooks);
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn fallible_generation() {
+        assert!(is_cuid(try_create_id().unwrap()));
+        assert_eq!(
+            Some(Error::InvalidLength(1)),
+            CuidConstructor::new().try_with_length(1).err()
+        );
+
+        let mut constructor = CuidConstructor::new().try_with_length(10).unwrap();
+        assert_eq!(Err(Error::InvalidLength(0)), constructor.try_set_length(0));
+        assert_eq!(10, constructor.try_create_id().unwrap().len());
+
+        struct BrokenClock;
+        impl Clock for BrokenClock {
+            fn now_millis(&self) -> u128 {
+                unreachable!()
+            }
+            fn try_now_millis(&self) -> Result<u128, ClockError> {
+                Err(ClockError)
+            }
+        }
+        let constructor = CuidConstructor::new().with_clock(BrokenClock);
+        assert_eq!(Err(Error::ClockBeforeEpoch), constructor.try_create_id());
+        assert_eq!(
+            Err(Error::ClockBeforeEpoch),
+            constructor.try_create_inline()
+        );
+        assert_eq!(
+            Some(Err(Error::ClockBeforeEpoch)),
+            constructor.try_iter().next()
+        );
+
+        assert_eq!(
+            Err(Error::TooLongToInline(33)),
+            CuidConstructor::new().with_length(33).try_create_inline()
+        );
+        assert_eq!(
+            Err(Error::TooLongToInline(34)),
+            CuidConstructor::new()
+                .with_length(30)
+                .with_prefix("usr")
+                .try_create_inline()
+        );
+        assert!(is_cuid(CuidConstructor::new().try_create_inline().unwrap()));
+    }
+    #[cfg(feature = "std")]
+    wasm_test!(fallible_generation);
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn batch_generation() {
+        let ids = create_ids(1000);
+        assert_eq!(1000, ids.len());
+        assert!(ids.iter().all(is_cuid2));
+        assert_eq!(1000, ids.iter().collect::<HashSet<_>>().len());
+
+        let mut ids = vec![create_id()];
+        CuidConstructor::new()
+            .with_length(10)
+            .extend_with_ids(&mut ids, 10);
+        assert_eq!(11, ids.len());
+        assert!(ids[1..].iter().all(|id| id.len() == 10));
+
+        // batches generate the same sequence as individual calls
+        let constructor = CuidConstructor::new().with_seed(42);
+        assert_eq!(
+            [
+                "p2qu386dsdkp1ekc980ozf3r",
+                "x3cwis8oafmxnslz4kbsynej",
+                "alrimr6rwkp3bcgzkw5chvji"
+            ],
+            constructor.create_ids(3).as_slice()
+        );
+
+        // the clock is read for every ID, so batches match individual calls
+        // even when the clock advances on every read
+        let ticking = || {
+            let ticks = AtomicU64::new(1_700_000_000_000);
+            CuidConstructor::new()
+                .with_seed(7)
+                .with_clock(move || u128::from(ticks.fetch_add(1, Ordering::Relaxed)))
+        };
+        let constructor = ticking();
+        let individual = (0..5).map(|_| constructor.create_id()).collect::<Vec<_>>();
+        assert_eq!(individual, ticking().create_ids(5));
+
+        struct BrokenClock;
+        impl Clock for BrokenClock {
+            fn now_millis(&self) -> u128 {
+                unreachable!()
+            }
+            fn try_now_millis(&self) -> Result<u128, ClockError> {
+                Err(ClockError)
+            }
+        }
+        let constructor = CuidConstructor::new().with_clock(BrokenClock);
+        assert_eq!(Err(Error::ClockBeforeEpoch), constructor.try_create_ids(5));
+        let mut ids = Vec::new();
+        assert_eq!(
+            Err(Error::ClockBeforeEpoch),
+            constructor.try_extend_with_ids(&mut ids, 5)
+        );
+        assert!(ids.is_empty());
+        assert_eq!(Ok(vec![]), constructor.try_create_ids(0));
+    }
+    #[cfg(feature = "std")]
+    wasm_test!(batch_generation);
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn iteration() {
+        let constructor = CuidConstructor::new().with_length(10);
+        let ids = constructor.iter().take(100).collect::<HashSet<_>>();
+        assert_eq!(100, ids.len());
+        assert!(ids.iter().all(|id| constructor.is_valid(id)));
+
+        let rows = ["alice", "bob"];
+        let zipped = (&constructor).into_iter().zip(rows).collect::<Vec<_>>();
+        assert_eq!(2, zipped.len());
+
+        // iterators generate the same sequence as individual calls
+        let constructor = CuidConstructor::new().with_seed(42);
+        assert_eq!(
+            vec![
+                "p2qu386dsdkp1ekc980ozf3r",
+                "x3cwis8oafmxnslz4kbsynej",
+                "alrimr6rwkp3bcgzkw5chvji"
+            ],
+            constructor.iter().take(3).collect::<Vec<_>>()
+        );
+
+        // like batches, iterators read the clock for every ID
+        let reads = Arc::new(AtomicU64::new(0));
+        let clock_reads = Arc::clone(&reads);
+        let constructor = CuidConstructor::new().with_clock(move || {
+            clock_reads.fetch_add(1, Ordering::Relaxed);
+            1_700_000_000_000
+        });
+        constructor.iter().take(10).for_each(drop);
+        assert_eq!(10, reads.load(Ordering::Relaxed));
+    }
+    #[cfg(feature = "std")]
+    wasm_test!(iteration);
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn prefixes() {
+        let constructor = CuidConstructor::new().with_prefix("usr");
+        let id = constructor.create_id();
+        assert_eq!(28, id.len());
+        assert_eq!(Ok(&id[4..]), constructor.strip_prefix(&id));
+        assert!(is_cuid2(&id[4..]));
+        assert!(!is_cuid2(&id));
+        assert!(!constructor.is_valid(&id[4..]));
+        assert!(!CuidConstructor::new().is_valid(&id));
+
+        let inline = constructor.create_inline();
+        assert!(constructor.is_valid(inline));
+        assert!(
+            constructor
+                .create_ids(3)
+                .iter()
+                .all(|id| constructor.is_valid(id))
+        );
+        assert!(
+            constructor
+                .iter()
+                .take(3)
+                .all(|id| constructor.is_valid(id))
+        );
+
+        // prefixes do not change the rest of the ID
+        let constructor = CuidConstructor::new().with_seed(42).with_prefix("ord");
+        assert_eq!("ord_p2qu386dsdkp1ekc980ozf3r", constructor.create_id());
+        let mut constructor = CuidConstructor::new().with_seed(42);
+        constructor.set_prefix("ord");
+        constructor.set_separator(':');
+        assert_eq!("ord:p2qu386dsdkp1ekc980ozf3r", constructor.create_id());
+
+        assert_eq!(
+            Some(Error::InvalidPrefix),
+            CuidConstructor::new().try_with_prefix("").err()
+        );
+        assert_eq!(
+            Some(Error::InvalidPrefix),
+            CuidConstructor::new().try_with_prefix("u_r").err()
+        );
+        assert_eq!(Ok(()), CuidConstructor::new().try_set_prefix("Usr2"));
+        assert_eq!(Err(Error::InvalidSeparator('a')), check_separator('a'));
+        assert_eq!(Err(Error::InvalidSeparator('·')), check_separator('·'));
+    }
+    #[cfg(feature = "std")]
+    wasm_test!(prefixes);
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn alphabets() {
+        // the default alphabet leaves IDs unchanged
+        let constructor = CuidConstructor::new()
+            .with_seed(42)
+            .with_alphabet(Alphabet::BASE36);
+        assert_eq!("p2qu386dsdkp1ekc980ozf3r", constructor.create_id());
+
+        // other alphabets re-encode the same hash
+        let constructor = CuidConstructor::new()
+            .with_seed(42)
+            .with_alphabet(Alphabet::BASE36_UPPER);
+        assert_eq!("P2QU386DSDKP1EKC980OZF3R", constructor.create_id());
+
+        let mut constructor = CuidConstructor::new().with_length(1000);
+        constructor.set_alphabet(Alphabet::UNAMBIGUOUS);
+        let id = constructor.create_id();
+        assert_eq!(1000, id.len());
+        assert!(id.chars().all(|c| Alphabet::UNAMBIGUOUS.contains(c)));
+        assert!(constructor.is_valid(&id));
+
+        let hex = CuidConstructor::new().with_alphabet(Alphabet::new("0123456789abcdef"));
+        let id = hex.create_id();
+        assert!(matches!(id.as_bytes()[0], b'a'..=b'f'));
+        assert!(hex.is_valid(&id));
+
+        let upper = CuidConstructor::new().with_alphabet(Alphabet::BASE36_UPPER);
+        assert!(upper.is_valid(upper.create_inline()));
+        assert!(!upper.is_valid(create_id()));
+        assert!(!CuidConstructor::new().is_valid(upper.create_id()));
+        assert_eq!(
+            Err(ValidationError::InvalidStart('1')),
+            upper.validate("1BCDEFGHIJKLMNOPQRSTUVWX")
+        );
+        assert_eq!(
+            Err(ValidationError::InvalidChar {
+                position: 2,
+                character: 'c'
+            }),
+            upper.validate("ABcDEFGHIJKLMNOPQRSTUVWX")
+        );
+    }
+    #[cfg(feature = "std")]
+    wasm_test!(alphabets);
+
+    #[cfg(not(target_family = "wasm"))]
+    proptest::proptest! {
+        #[test]
+        fn digest_encoding_matches_biguint(
+            input: Vec<u8>,
+            length in 1_usize..300,
+            alphabet in proptest::sample::select(vec![
+                Alphabet::BASE36,
+                Alphabet::BASE36_UPPER,
+                Alphabet::UNAMBIGUOUS,
+                Alphabet::new("ab"),
+            ]),
+        ) {
+            // Convert each digest in full with BigUint, keeping only as many
+            // of its low-order digits as are still needed
+            let mut hash = Sha3_512::digest(&input);
+            let mut expected = String::new();
+            loop {
+                let digits = bigint::BigUint::from_bytes_be(&hash)
+                    .to_radix_be(alphabet.radix() as u32);
+                let remaining = length - expected.len();
+                expected.extend(
+                    digits[digits.len().saturating_sub(remaining)..]
+                        .iter()
+                        .map(|&digit| char::from(alphabet.digit(digit))),
+                );
+                if expected.len() == length {
+                    break;
+                }
+                hash = Sha3_512::digest(hash);
+            }
+
+            let mut hasher = Sha3_512::new();
+            hasher.update(&input);
+            let mut encoded = String::new();
+            encode_digest(hasher, length, &alphabet, |ch| encoded.push(ch));
+            assert_eq!(expected, encoded);
+        }
+    }
+
+    #[cfg(feature = "futures")]
+    #[test]
+    fn streaming() {
+        use core::{
+            pin::pin,
+            task::{Context, Poll, Waker},
+        };
+
+        use futures_core::Stream;
+
+        fn assert_send<T: Send>(_: &T) {}
+
+        let constructor = CuidConstructor::new().with_seed(42);
+        let mut stream = pin!(constructor.stream());
+        assert_send(&stream);
+
+        let mut cx = Context::from_waker(Waker::noop());
+        for expected in [
+            "p2qu386dsdkp1ekc980ozf3r",
+            "x3cwis8oafmxnslz4kbsynej",
+            "alrimr6rwkp3bcgzkw5chvji",
+        ] {
+            assert_eq!(
+                Poll::Ready(Some(String::from(expected))),
+                stream.as_mut().poll_next(&mut cx)
+            );
+        }
+        assert_eq!((usize::MAX, None), stream.size_hint());
+
+        let constructor = CuidConstructor::new().with_clock(|| 0).with_seed(42);
+        let mut stream = pin!(constructor.try_stream());
+        assert!(matches!(
+            stream.as_mut().poll_next(&mut cx),
+            Poll::Ready(Some(Ok(id))) if is_cuid(&id)
+        ));
+    }
+    #[cfg(feature = "futures")]
+    wasm_test!(streaming);
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn long_ids() {
+        for length in [98, 99, 100, 101, 200, 1000, u16::MAX] {
+            let id = CuidConstructor::new().with_length(length).create_id();
+            assert_eq!(usize::from(length), id.len());
+            assert!(is_cuid(&id[..32]));
+            assert!(id.bytes().all(|b| BASE_36_DIGITS.contains(&b)));
+        }
+
+        // Chained digests are still reproducible from a seed
+        let first = CuidConstructor::new().with_seed(7).with_length(300);
+        let second = CuidConstructor::new().with_seed(7).with_length(300);
+        let id = first.create_id();
+        assert_eq!(id, second.create_id());
+        assert_ne!(id[1..100], id[100..199]);
+    }
+    #[cfg(feature = "std")]
+    wasm_test!(long_ids);
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn constructor_validation() {
+        let constructor = CuidConstructor::new().with_length(16);
+        assert!(constructor.is_valid(constructor.create_id()));
+        assert_eq!(
+            Err(ValidationError::TooShort {
+                length: 10,
+                min: 16
+            }),
+            constructor.validate(slug())
+        );
+        assert_eq!(
+            Err(ValidationError::TooLong {
+                length: 24,
+                max: 16
+            }),
+            constructor.validate(create_id())
+        );
+        assert_eq!(
+            Err(ValidationError::InvalidStart('0')),
+            constructor.validate("0123456789abcdef")
+        );
+
+        // Lengths beyond those accepted by `is_cuid2()` are still validated
+        let long = CuidConstructor::new().with_length(200);
+        assert!(long.is_valid(long.create_id()));
+    }
+    #[cfg(feature = "std")]
+    wasm_test!(constructor_validation);
+
+    #[cfg(feature = "std")]
     #[cfg(not(target_family = "wasm"))] // uses num_cpus, which we can't compile on wasm
     #[test]
     #[ignore] // slow: run explicitly when desired
@@ -526,6 +2280,7 @@ mod test {
         assert_eq!(res.iter().collect::<HashSet<_>>().len(), res.len())
     }
 
+    #[cfg(feature = "std")]
     /// Asserts that CUIDs are uniformly distributed, ignoring the first
     /// character.
     ///
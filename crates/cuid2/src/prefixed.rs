@@ -0,0 +1,482 @@
+//! Distinct types for prefixed CUIDs.
+//!
+//! See [`define_cuid!`](crate::define_cuid).
+
+/// Defines a type for CUIDs with a particular prefix.
+///
+/// Each type is distinct, so a `UserId` cannot be passed where an `OrderId`
+/// is expected, and may only be obtained by generating an ID or by parsing a
+/// string with the right prefix. IDs are generated by a
+/// [`CuidConstructor`](crate::CuidConstructor) with the given prefix and
+/// separator (`_` by default), which is available via the type's
+/// `constructor()` function.
+///
+/// The type implements the same traits as [`Cuid2`](crate::Cuid2),
+/// including `Serialize` and `Deserialize` with the `serde` feature,
+/// sqlx's `Type`, `Encode`, and `Decode` with the `sqlx` feature,
+/// Diesel's `Text` column support with the `diesel` feature, and rusqlite's
+/// `ToSql` and `FromSql` with the `rusqlite` feature.
+///
+/// ```
+/// cuid2::define_cuid!(
+///     /// The ID of a user.
+///     pub UserId, "usr"
+/// );
+/// cuid2::define_cuid!(pub OrderId, "ord", '-');
+///
+/// let user = UserId::new();
+/// assert!(user.as_str().starts_with("usr_"));
+/// assert_eq!(user, user.as_str().parse().unwrap());
+///
+/// let order = OrderId::new();
+/// assert!(order.as_str().starts_with("ord-"));
+/// assert!(order.as_str().parse::<UserId>().is_err());
+/// ```
+#[macro_export]
+macro_rules! define_cuid {
+    ($(#[$meta:meta])* $vis:vis $name:ident, $prefix:expr $(, $separator:expr)? $(,)?) => {
+        $(#[$meta])*
+        #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        $vis struct $name($crate::__private::String);
+        #[allow(dead_code)]
+        impl $name {
+            /// The prefix of every ID of this type.
+            pub const PREFIX: &'static str = $prefix;
+
+            /// Returns the constructor used to generate and validate IDs of
+            /// this type.
+            pub fn constructor() -> &'static $crate::CuidConstructor {
+                static CONSTRUCTOR: $crate::CuidConstructor =
+                    $crate::CuidConstructor::new().with_prefix($prefix) $(.with_separator($separator))?;
+                &CONSTRUCTOR
+            }
+
+            /// Returns the ID as a string slice.
+            #[inline]
+            pub fn as_str(&self) -> &str {
+                &self.0
+            }
+
+            /// Returns the ID without its prefix and separator.
+            #[inline]
+            pub fn without_prefix(&self) -> &str {
+                // The separator is always a single byte
+                &self.0[Self::PREFIX.len() + 1..]
+            }
+
+            /// Consumes the ID, returning the inner `String`.
+            #[inline]
+            pub fn into_string(self) -> $crate::__private::String {
+                self.0
+            }
+        }
+        impl ::core::fmt::Display for $name {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                f.write_str(&self.0)
+            }
+        }
+        impl ::core::convert::AsRef<str> for $name {
+            fn as_ref(&self) -> &str {
+                &self.0
+            }
+        }
+        impl ::core::borrow::Borrow<str> for $name {
+            fn borrow(&self) -> &str {
+                &self.0
+            }
+        }
+        impl ::core::convert::From<$name> for $crate::__private::String {
+            fn from(id: $name) -> Self {
+                id.0
+            }
+        }
+        impl ::core::str::FromStr for $name {
+            type Err = $crate::ValidationError;
+
+            fn from_str(s: &str) -> ::core::result::Result<Self, Self::Err> {
+                Self::try_from(s)
+            }
+        }
+        impl ::core::convert::TryFrom<&str> for $name {
+            type Error = $crate::ValidationError;
+
+            fn try_from(value: &str) -> ::core::result::Result<Self, Self::Error> {
+                Self::constructor().validate(value)?;
+                ::core::result::Result::Ok(Self(value.into()))
+            }
+        }
+        impl ::core::convert::TryFrom<$crate::__private::String> for $name {
+            type Error = $crate::ValidationError;
+
+            fn try_from(
+                value: $crate::__private::String,
+            ) -> ::core::result::Result<Self, Self::Error> {
+                Self::constructor().validate(&value)?;
+                ::core::result::Result::Ok(Self(value))
+            }
+        }
+        $crate::__define_cuid_std!($name);
+        $crate::__define_cuid_serde!($name);
+        $crate::__define_cuid_sqlx!($name);
+        $crate::__define_cuid_diesel!($name);
+        $crate::__define_cuid_rusqlite!($name);
+    };
+}
+
+/// Implements the parts of [`define_cuid!`] which require the `std` feature
+/// of this crate. Since macros are expanded in the calling crate, this
+/// cannot be done with `#[cfg]` attributes in `define_cuid!` itself.
+#[cfg(feature = "std")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __define_cuid_std {
+    ($name:ident) => {
+        #[allow(dead_code)]
+        impl $name {
+            /// Generates a new ID.
+            #[inline]
+            pub fn new() -> Self {
+                Self(Self::constructor().create_id())
+            }
+        }
+        impl ::core::default::Default for $name {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+    };
+}
+
+#[cfg(not(feature = "std"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __define_cuid_std {
+    ($name:ident) => {};
+}
+
+/// Implements `Serialize` and `Deserialize` for types from [`define_cuid!`]
+/// if the `serde` feature of this crate is enabled.
+#[cfg(feature = "serde")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __define_cuid_serde {
+    ($name:ident) => {
+        impl $crate::__private::serde::Serialize for $name {
+            fn serialize<S: $crate::__private::serde::Serializer>(
+                &self,
+                serializer: S,
+            ) -> ::core::result::Result<S::Ok, S::Error> {
+                serializer.serialize_str(&self.0)
+            }
+        }
+        impl<'de> $crate::__private::serde::Deserialize<'de> for $name {
+            fn deserialize<D: $crate::__private::serde::Deserializer<'de>>(
+                deserializer: D,
+            ) -> ::core::result::Result<Self, D::Error> {
+                let value = <$crate::__private::String as $crate::__private::serde::Deserialize>::deserialize(
+                    deserializer,
+                )?;
+                Self::try_from(value).map_err(<D::Error as $crate::__private::serde::de::Error>::custom)
+            }
+        }
+    };
+}
+
+#[cfg(not(feature = "serde"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __define_cuid_serde {
+    ($name:ident) => {};
+}
+
+/// Implements sqlx's `Type`, `Encode`, and `Decode` for [`Cuid2`] and types
+/// from [`define_cuid!`] if the `sqlx` feature of this crate is enabled.
+///
+/// [`Cuid2`]: crate::Cuid2
+#[cfg(feature = "sqlx")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __define_cuid_sqlx {
+    ($name:ident) => {
+        impl<DB: $crate::__private::sqlx::Database> $crate::__private::sqlx::Type<DB> for $name
+        where
+            str: $crate::__private::sqlx::Type<DB>,
+        {
+            fn type_info() -> DB::TypeInfo {
+                <str as $crate::__private::sqlx::Type<DB>>::type_info()
+            }
+
+            fn compatible(ty: &DB::TypeInfo) -> bool {
+                <str as $crate::__private::sqlx::Type<DB>>::compatible(ty)
+            }
+        }
+        impl<'q, DB: $crate::__private::sqlx::Database> $crate::__private::sqlx::Encode<'q, DB>
+            for $name
+        where
+            for<'a> &'a str: $crate::__private::sqlx::Encode<'q, DB>,
+        {
+            fn encode_by_ref(
+                &self,
+                buf: &mut DB::ArgumentBuffer,
+            ) -> ::core::result::Result<
+                $crate::__private::sqlx::encode::IsNull,
+                $crate::__private::sqlx::error::BoxDynError,
+            > {
+                <&str as $crate::__private::sqlx::Encode<DB>>::encode(self.as_str(), buf)
+            }
+
+            fn produces(&self) -> ::core::option::Option<DB::TypeInfo> {
+                <&str as $crate::__private::sqlx::Encode<DB>>::produces(&self.as_str())
+            }
+
+            fn size_hint(&self) -> usize {
+                <&str as $crate::__private::sqlx::Encode<DB>>::size_hint(&self.as_str())
+            }
+        }
+        impl<'r, DB: $crate::__private::sqlx::Database> $crate::__private::sqlx::Decode<'r, DB>
+            for $name
+        where
+            &'r str: $crate::__private::sqlx::Decode<'r, DB>,
+        {
+            fn decode(
+                value: DB::ValueRef<'r>,
+            ) -> ::core::result::Result<Self, $crate::__private::sqlx::error::BoxDynError> {
+                let value = <&str as $crate::__private::sqlx::Decode<DB>>::decode(value)?;
+                ::core::result::Result::Ok(Self::try_from(value)?)
+            }
+        }
+    };
+}
+
+#[cfg(not(feature = "sqlx"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __define_cuid_sqlx {
+    ($name:ident) => {};
+}
+
+/// Implements Diesel's `AsExpression`, `ToSql`, `FromSql`, and `Queryable`
+/// for `Text` columns for [`Cuid2`] and types from [`define_cuid!`] if the
+/// `diesel` feature of this crate is enabled. Diesel's derives cannot be
+/// used, since they refer to `diesel`, which the calling crate may not
+/// depend on.
+///
+/// [`Cuid2`]: crate::Cuid2
+#[cfg(feature = "diesel")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __define_cuid_diesel {
+    ($name:ident) => {
+        $crate::__define_cuid_diesel!(@expression $name, $crate::__private::diesel::sql_types::Text);
+        $crate::__define_cuid_diesel!(
+            @expression $name,
+            $crate::__private::diesel::sql_types::Nullable<$crate::__private::diesel::sql_types::Text>
+        );
+        impl<DB: $crate::__private::diesel::backend::Backend>
+            $crate::__private::diesel::serialize::ToSql<$crate::__private::diesel::sql_types::Text, DB>
+            for $name
+        where
+            str: $crate::__private::diesel::serialize::ToSql<
+                    $crate::__private::diesel::sql_types::Text,
+                    DB,
+                >,
+        {
+            fn to_sql<'b>(
+                &'b self,
+                out: &mut $crate::__private::diesel::serialize::Output<'b, '_, DB>,
+            ) -> $crate::__private::diesel::serialize::Result {
+                <str as $crate::__private::diesel::serialize::ToSql<
+                    $crate::__private::diesel::sql_types::Text,
+                    DB,
+                >>::to_sql(self.as_str(), out)
+            }
+        }
+        impl<DB: $crate::__private::diesel::backend::Backend>
+            $crate::__private::diesel::deserialize::FromSql<$crate::__private::diesel::sql_types::Text, DB>
+            for $name
+        where
+            $crate::__private::String: $crate::__private::diesel::deserialize::FromSql<
+                    $crate::__private::diesel::sql_types::Text,
+                    DB,
+                >,
+        {
+            fn from_sql(
+                bytes: DB::RawValue<'_>,
+            ) -> $crate::__private::diesel::deserialize::Result<Self> {
+                let value = <$crate::__private::String as $crate::__private::diesel::deserialize::FromSql<
+                    $crate::__private::diesel::sql_types::Text,
+                    DB,
+                >>::from_sql(bytes)?;
+                ::core::result::Result::Ok(Self::try_from(value)?)
+            }
+        }
+        impl<DB, ST> $crate::__private::diesel::deserialize::Queryable<ST, DB> for $name
+        where
+            DB: $crate::__private::diesel::backend::Backend,
+            ST: $crate::__private::diesel::sql_types::SingleValue,
+            Self: $crate::__private::diesel::deserialize::FromSql<ST, DB>,
+        {
+            type Row = Self;
+
+            fn build(row: Self) -> $crate::__private::diesel::deserialize::Result<Self> {
+                ::core::result::Result::Ok(row)
+            }
+        }
+    };
+    (@expression $name:ident, $sql_type:ty) => {
+        impl $crate::__private::diesel::expression::AsExpression<$sql_type> for $name {
+            type Expression = <$crate::__private::String as $crate::__private::diesel::expression::AsExpression<$sql_type>>::Expression;
+
+            fn as_expression(self) -> Self::Expression {
+                $crate::__private::diesel::expression::AsExpression::<$sql_type>::as_expression(
+                    self.into_string(),
+                )
+            }
+        }
+        impl<'a> $crate::__private::diesel::expression::AsExpression<$sql_type> for &'a $name {
+            type Expression = <&'a str as $crate::__private::diesel::expression::AsExpression<$sql_type>>::Expression;
+
+            fn as_expression(self) -> Self::Expression {
+                $crate::__private::diesel::expression::AsExpression::<$sql_type>::as_expression(
+                    self.as_str(),
+                )
+            }
+        }
+    };
+}
+
+#[cfg(not(feature = "diesel"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __define_cuid_diesel {
+    ($name:ident) => {};
+}
+
+/// Implements rusqlite's `ToSql` and `FromSql` for [`Cuid2`] and types from
+/// [`define_cuid!`] if the `rusqlite` feature of this crate is enabled.
+///
+/// [`Cuid2`]: crate::Cuid2
+#[cfg(feature = "rusqlite")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __define_cuid_rusqlite {
+    ($name:ident) => {
+        impl $crate::__private::rusqlite::types::ToSql for $name {
+            fn to_sql(
+                &self,
+            ) -> $crate::__private::rusqlite::Result<
+                $crate::__private::rusqlite::types::ToSqlOutput<'_>,
+            > {
+                ::core::result::Result::Ok(self.as_str().into())
+            }
+        }
+        impl $crate::__private::rusqlite::types::FromSql for $name {
+            fn column_result(
+                value: $crate::__private::rusqlite::types::ValueRef<'_>,
+            ) -> $crate::__private::rusqlite::types::FromSqlResult<Self> {
+                Self::try_from(value.as_str()?).map_err(|err| {
+                    $crate::__private::rusqlite::types::FromSqlError::Other(::std::boxed::Box::new(
+                        err,
+                    ))
+                })
+            }
+        }
+    };
+}
+
+#[cfg(not(feature = "rusqlite"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __define_cuid_rusqlite {
+    ($name:ident) => {};
+}
+
+#[cfg(test)]
+mod test {
+    #[cfg(feature = "std")]
+    use std::collections::HashSet;
+
+    #[cfg(feature = "std")]
+    use crate::ValidationError;
+
+    crate::define_cuid!(UserId, "usr");
+    crate::define_cuid!(
+        /// An order ID.
+        pub(crate) OrderId,
+        "ord",
+        '-',
+    );
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn generated_ids_are_valid() {
+        let id = UserId::new();
+        assert_eq!("usr", UserId::PREFIX);
+        assert!(id.as_str().starts_with("usr_"));
+        assert_eq!(28, id.as_str().len());
+        assert!(crate::is_cuid2(id.without_prefix()));
+        assert!(UserId::constructor().is_valid(&id));
+
+        let id = OrderId::default();
+        assert!(id.as_str().starts_with("ord-"));
+        assert!(crate::is_cuid2(id.without_prefix()));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn parse_round_trip() {
+        let id = UserId::new();
+        assert_eq!(id, id.to_string().parse().unwrap());
+        assert_eq!(id, UserId::try_from(id.as_str()).unwrap());
+        assert_eq!(id, UserId::try_from(id.clone().into_string()).unwrap());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn parse_invalid() {
+        let order = OrderId::new();
+        assert_eq!(
+            Err(ValidationError::MissingPrefix),
+            order.as_str().parse::<UserId>()
+        );
+        assert_eq!(
+            Err(ValidationError::MissingPrefix),
+            order.without_prefix().parse::<OrderId>()
+        );
+        assert_eq!(
+            Err(ValidationError::MissingPrefix),
+            format!("usr-{}", order.without_prefix()).parse::<UserId>()
+        );
+        assert_eq!(
+            Err(ValidationError::InvalidChar {
+                position: 5,
+                character: 'B'
+            }),
+            "usr_aBcdefghijklmnopqrstuvwx".parse::<UserId>()
+        );
+        assert_eq!(
+            Err(ValidationError::TooShort { length: 7, min: 28 }),
+            "usr_abc".parse::<UserId>()
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn lookup_by_str() {
+        let id = UserId::new();
+        let set = HashSet::from([id.clone()]);
+        assert!(set.contains(id.as_str()));
+    }
+
+    #[cfg(all(feature = "serde", feature = "std"))]
+    #[test]
+    fn serde_round_trip() {
+        let id = UserId::new();
+        let json = serde_json::to_string(&id).unwrap();
+        assert_eq!(format!("\"{id}\""), json);
+        assert_eq!(id, serde_json::from_str(&json).unwrap());
+
+        let order = serde_json::to_string(&OrderId::new()).unwrap();
+        assert!(serde_json::from_str::<UserId>(&order).is_err());
+    }
+}
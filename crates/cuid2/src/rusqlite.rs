@@ -0,0 +1,106 @@
+//! rusqlite support for CUID2s, enabled with the `rusqlite` feature.
+//!
+//! [`Cuid2`](crate::Cuid2) implements `ToSql` and `FromSql`, storing IDs as text, and
+//! failing to read any value which is not a valid CUID2.
+//!
+//! [`register_function()`] adds a `cuid2()` SQL function to a connection,
+//! which may be used as a column default:
+//!
+//! ```
+//! use cuid2::Cuid2;
+//! use rusqlite::Connection;
+//!
+//! let conn = Connection::open_in_memory()?;
+//! cuid2::rusqlite::register_function(&conn)?;
+//! conn.execute_batch(
+//!     "CREATE TABLE users (id TEXT PRIMARY KEY DEFAULT (cuid2()), name TEXT);
+//!      INSERT INTO users (name) VALUES ('alice');",
+//! )?;
+//!
+//! let id: Cuid2 = conn.query_row("SELECT id FROM users", [], |row| row.get(0))?;
+//! # Ok::<(), rusqlite::Error>(())
+//! ```
+//!
+//! SQLite does not store functions in the database, so they must be
+//! registered on every connection which inserts rows relying on the default.
+
+use ::rusqlite::{Connection, Error, Result, functions::FunctionFlags};
+
+use crate::{CuidConstructor, DEFAULT_CONSTRUCTOR};
+
+/// Registers a `cuid2()` SQL function on the connection, which generates an
+/// ID with the default settings each time it is called.
+pub fn register_function(conn: &Connection) -> Result<()> {
+    register_constructor(conn, "cuid2", &DEFAULT_CONSTRUCTOR)
+}
+
+/// Registers a SQL function named `name` on the connection, which generates
+/// an ID with `constructor` each time it is called.
+///
+/// This is useful for IDs with a custom length or prefix:
+///
+/// ```
+/// cuid2::define_cuid!(pub UserId, "usr");
+///
+/// let conn = rusqlite::Connection::open_in_memory()?;
+/// cuid2::rusqlite::register_constructor(&conn, "user_id", UserId::constructor())?;
+/// let id: UserId = conn.query_row("SELECT user_id()", [], |row| row.get(0))?;
+/// # Ok::<(), rusqlite::Error>(())
+/// ```
+pub fn register_constructor(
+    conn: &Connection,
+    name: &str,
+    constructor: &'static CuidConstructor,
+) -> Result<()> {
+    conn.create_scalar_function(
+        name,
+        0,
+        // Not deterministic, so that SQLite calls the function for every row,
+        // but innocuous, so that it may be used in the schema
+        FunctionFlags::SQLITE_UTF8 | FunctionFlags::SQLITE_INNOCUOUS,
+        |_| {
+            constructor
+                .try_create_id()
+                .map_err(|err| Error::UserFunctionError(Box::new(err)))
+        },
+    )
+}
+
+#[cfg(all(test, not(target_family = "wasm")))]
+mod test {
+    use super::*;
+    use crate::{Cuid2, is_cuid2};
+
+    #[test]
+    fn default_value() {
+        let conn = Connection::open_in_memory().unwrap();
+        register_function(&conn).unwrap();
+        conn.execute_batch(
+            "CREATE TABLE users (id TEXT PRIMARY KEY DEFAULT (cuid2()), name TEXT);
+             INSERT INTO users (name) VALUES ('alice'), ('bob');",
+        )
+        .unwrap();
+
+        let mut stmt = conn.prepare("SELECT id FROM users").unwrap();
+        let ids = stmt
+            .query_map([], |row| row.get::<_, Cuid2>(0))
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(2, ids.len());
+        assert_ne!(ids[0], ids[1]);
+    }
+
+    #[test]
+    fn custom_constructor() {
+        static CONSTRUCTOR: CuidConstructor = CuidConstructor::new().with_length(10);
+
+        let conn = Connection::open_in_memory().unwrap();
+        register_constructor(&conn, "short_id", &CONSTRUCTOR).unwrap();
+        let id: String = conn
+            .query_row("SELECT short_id()", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(10, id.len());
+        assert!(is_cuid2(&id));
+    }
+}
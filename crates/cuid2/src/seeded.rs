@@ -0,0 +1,73 @@
+//! State for deterministic CUID generation.
+//!
+//! See [`CuidConstructor::with_seed()`](crate::CuidConstructor::with_seed).
+
+use alloc::string::String;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+use rand::{RngExt, SeedableRng, rngs::ChaCha8Rng};
+
+use crate::{BIG_LENGTH, MAX_COUNTER_INIT, hash};
+
+/// The timestamp used for seeded CUIDs.
+pub(crate) const SEEDED_TIMESTAMP: u128 = 0;
+
+/// The ChaCha stream used to derive the counter and fingerprint from the
+/// seed. Streams for individual IDs count up from zero, so this one will not
+/// be reached in practice.
+const SETUP_STREAM: u64 = u64::MAX;
+
+/// Replaces all of the sources of randomness for a constructor with values
+/// derived from a seed.
+///
+/// Each generated ID gets its own RNG, seeded with the constructor's seed and
+/// using the ID's sequence number as the ChaCha stream. This allows us to
+/// generate IDs from a shared reference without locking.
+pub(crate) struct Seeded {
+    seed: u64,
+    counter_init: u64,
+    fingerprint: String,
+    /// A `usize` rather than a `u64`, so that seeding is available on targets
+    /// without 64-bit atomics. On those targets, the sequence wraps after
+    /// `usize::MAX` IDs.
+    sequence: AtomicUsize,
+}
+impl Seeded {
+    pub(crate) fn new(seed: u64) -> Self {
+        let mut rng = stream_rng(seed, SETUP_STREAM);
+        Self {
+            seed,
+            counter_init: rng.random_range(0..MAX_COUNTER_INIT),
+            fingerprint: hash(
+                [
+                    rng.random::<u128>().to_be_bytes(),
+                    rng.random::<u128>().to_be_bytes(),
+                ],
+                BIG_LENGTH.into(),
+            ),
+            sequence: AtomicUsize::new(0),
+        }
+    }
+
+    /// Returns the RNG and counter value to use for the next ID.
+    #[inline]
+    pub(crate) fn next(&self) -> (ChaCha8Rng, u64) {
+        // Cast safety: usize is at most 64 bits on all supported targets.
+        let sequence = self.sequence.fetch_add(1, Ordering::Relaxed) as u64;
+        (
+            stream_rng(self.seed, sequence),
+            self.counter_init.wrapping_add(sequence),
+        )
+    }
+
+    #[inline]
+    pub(crate) fn fingerprint(&self) -> &str {
+        &self.fingerprint
+    }
+}
+
+fn stream_rng(seed: u64, stream: u64) -> ChaCha8Rng {
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    rng.set_stream(stream);
+    rng
+}
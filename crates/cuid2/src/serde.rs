@@ -0,0 +1,144 @@
+//! Serde support for CUID2s, enabled with the `serde` feature.
+//!
+//! [`Cuid2`] implements `Serialize` and `Deserialize` directly, serializing
+//! as a string and failing to deserialize any string which is not a valid
+//! CUID2.
+//!
+//! For existing `String` fields, the modules in this module may be used with
+//! `#[serde(with = "...")]` to get the same validation without changing the
+//! type of the field:
+//!
+//! ```
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct User {
+//!     #[serde(with = "cuid2::serde::string")]
+//!     id: String,
+//!     #[serde(with = "cuid2::serde::slug")]
+//!     short_id: String,
+//! }
+//!
+//! let user: Result<User, _> = serde_json::from_str(r#"{"id": "nope!", "short_id": "a1b2c3"}"#);
+//! assert!(user.is_err());
+//! ```
+
+use ::serde::{Deserialize, Deserializer, Serialize, Serializer, de};
+use alloc::string::String;
+
+use crate::{Cuid2, is_cuid2, is_slug};
+
+impl Serialize for Cuid2 {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+impl<'de> Deserialize<'de> for Cuid2 {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        string::deserialize(deserializer).map(Cuid2::from_string_unchecked)
+    }
+}
+
+/// Deserialize a string, failing if it does not pass `check`.
+fn deserialize_checked<'de, D: Deserializer<'de>>(
+    deserializer: D,
+    check: fn(&str) -> bool,
+    expected: &'static str,
+) -> Result<String, D::Error> {
+    let value = String::deserialize(deserializer)?;
+    if check(&value) {
+        Ok(value)
+    } else {
+        Err(de::Error::invalid_value(
+            de::Unexpected::Str(&value),
+            &expected,
+        ))
+    }
+}
+
+/// Validate `String` fields as CUID2s, with `#[serde(with = "cuid2::serde::string")]`.
+pub mod string {
+    use super::*;
+
+    /// Serialize the string as-is.
+    pub fn serialize<S: Serializer>(value: &str, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(value)
+    }
+
+    /// Deserialize a string, failing if it is not a valid CUID2.
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+        deserialize_checked(deserializer, |value| is_cuid2(value), "a CUID2")
+    }
+}
+
+/// Validate `String` fields as CUID2 slugs, with `#[serde(with = "cuid2::serde::slug")]`.
+pub mod slug {
+    use super::*;
+
+    /// Serialize the string as-is.
+    pub fn serialize<S: Serializer>(value: &str, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(value)
+    }
+
+    /// Deserialize a string, failing if it is not a valid CUID2 slug.
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+        deserialize_checked(deserializer, |value| is_slug(value), "a CUID2 slug")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #[cfg(feature = "std")]
+    use ::serde::{Deserialize, Serialize};
+
+    use crate::Cuid2;
+    #[cfg(feature = "std")]
+    use crate::{create_id, slug};
+
+    #[cfg(feature = "std")]
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Record {
+        #[serde(with = "super::string")]
+        id: String,
+        #[serde(with = "super::slug")]
+        slug: String,
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn cuid2_round_trip() {
+        let id = Cuid2::new();
+        let json = serde_json::to_string(&id).unwrap();
+        assert_eq!(format!("\"{id}\""), json);
+        assert_eq!(id, serde_json::from_str(&json).unwrap());
+    }
+
+    #[test]
+    fn cuid2_invalid() {
+        let err = serde_json::from_str::<Cuid2>("\"Not-A-Cuid\"").unwrap_err();
+        assert!(err.to_string().contains("expected a CUID2"), "{err}");
+        assert!(serde_json::from_str::<Cuid2>("12").is_err());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn helpers_round_trip() {
+        let record = Record {
+            id: create_id(),
+            slug: slug(),
+        };
+        let json = serde_json::to_string(&record).unwrap();
+        assert_eq!(record, serde_json::from_str(&json).unwrap());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn helpers_invalid() {
+        let long = create_id();
+        assert!(
+            serde_json::from_str::<Record>(&format!(r#"{{"id": "{long}", "slug": "{long}"}}"#))
+                .is_err()
+        );
+        assert!(serde_json::from_str::<Record>(r#"{"id": "1abc", "slug": "abc"}"#).is_err());
+    }
+}